@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// Lines starting with one of these are Directives, not a Label.
+pub(super) const DIRECTIVES: &[&str] =
+    &["word", "byte", "string", "zero", "equ", "alias"];
+
+/// Names a Program gave to Numbers and Registers.
+///
+/// ### Dev Metadata
+/// - `.equ SIZE 32` -> `SIZE` is replaced by `32`
+///     - the Literal is kept as written, so `.equ PORT 0xFF02` stays
+///       an Address and `.equ MASK $0xFF` an Immediate
+/// - `.alias x gce` -> `x` is replaced by `gce`, also inside of `[x+2]`
+/// - Arguments are replaced before [parse_arg] sees them,
+///   Names have to be defined before Data Directives use them
+#[derive(Default)]
+pub(super) struct Definitions {
+    constants: HashMap<String, String>,
+    aliases: HashMap<String, String>,
+}
+
+impl Definitions {
+    pub(super) fn define(
+        &mut self,
+        directive: &str,
+        args: &[&str],
+    ) -> Result<(), RizeError> {
+        let [name, value] = args else {
+            return Err(RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!("Expected '.{} name value'.", directive),
+            });
+        };
+        self.check_name(name)?;
+
+        if directive == "alias" {
+            if !is_register_name(value) {
+                return Err(RizeError {
+                    type_: RizeErrorType::Assemble,
+                    message: format!(
+                        "Alias '{}' has to name a Register, not '{}'.",
+                        name, value
+                    ),
+                });
+            }
+            self.aliases.insert(name.to_string(), value.to_string());
+            return Ok(());
+        }
+
+        let expanded = self.expand(value);
+        if literal(&expanded).is_none() {
+            return Err(RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!(
+                    "Constant '{}' has to be a Number, not '{}'.",
+                    name, value
+                ),
+            });
+        }
+        self.constants.insert(name.to_string(), expanded);
+        Ok(())
+    }
+
+    /// Replaces Constants and Aliases in a single Argument.
+    pub(super) fn expand(&self, raw: &str) -> String {
+        if let Some(value) = self.constants.get(raw) {
+            return value.clone();
+        }
+        if let Some(register) = self.aliases.get(raw) {
+            return register.clone();
+        }
+
+        let Some(inner) =
+            raw.strip_prefix('[').and_then(|raw| raw.strip_suffix(']'))
+        else {
+            return raw.to_string();
+        };
+        let (register, offset) = match inner.find(['+', '-']) {
+            Some(split) => inner.split_at(split),
+            None => (inner, ""),
+        };
+        let register = self.aliases.get(register).map_or(register, |r| r);
+        let offset = match offset.split_at_checked(1) {
+            Some((sign, name)) if self.constants.contains_key(name) => {
+                let value = literal(&self.constants[name]).unwrap_or_default();
+                let value = if sign == "-" { -value } else { value };
+                format!("{:+}", value)
+            }
+            _ => offset.to_string(),
+        };
+        format!("[{}{}]", register, offset)
+    }
+
+    fn check_name(&self, name: &str) -> Result<(), RizeError> {
+        let error = |reason: &str| RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!("Can't define '{}', {}.", name, reason),
+        };
+        if !is_identifier(name) {
+            return Err(error(
+                "Names start with a Letter, followed by Letters, Digits or '_'",
+            ));
+        }
+        if is_register_name(name) {
+            return Err(error("it is a Register"));
+        }
+        if name.parse::<OpCode>().is_ok() {
+            return Err(error("it is an OpCode"));
+        }
+        if self.constants.contains_key(name) || self.aliases.contains_key(name)
+        {
+            return Err(error("it is already defined"));
+        }
+        Ok(())
+    }
+}
+
+/// Turns a Data Directive into the Words it places into Memory.
+/// Without `symbols` (during the First Pass), Labels count as 0,
+/// which is enough to know the Size.
+///
+/// ### Dev Metadata
+/// | Directive          | Words                                         |
+/// | ------------------ | --------------------------------------------- |
+/// | `.word 1 -2 .x`    | one per Value, Literals or Labels             |
+/// | `.byte 1 2 3`      | two Bytes per Word, the first one in the High Byte |
+/// | `.string "Hi\n"`   | one per Character, plus a terminating 0       |
+/// | `.zero 16`         | that many 0s                                  |
+///
+/// - `.word $.x` is the same as `.word .x`, a Label's Address
+pub(super) fn assemble_data(
+    name: &str,
+    args: &[&str],
+    scope: &str,
+    definitions: &Definitions,
+    symbols: Option<&HashMap<String, u16>>,
+) -> Result<Vec<u16>, RizeError> {
+    let expanded: Vec<String> = args
+        .iter()
+        .map(|arg| definitions.expand(&scope_local_label(arg, scope)))
+        .collect();
+    let args: Vec<&str> = expanded.iter().map(String::as_str).collect();
+
+    let invalid = |raw: &str, expected: &str| RizeError {
+        type_: RizeErrorType::Assemble,
+        message: format!(
+            "Invalid value '{}' for '.{}', expected {}.",
+            raw, name, expected
+        ),
+    };
+    if args.is_empty() {
+        return Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!("'.{}' needs at least one value.", name),
+        });
+    }
+
+    match name {
+        "word" => args
+            .iter()
+            .map(|raw| raw.strip_prefix('$').unwrap_or(raw))
+            .map(|raw| match raw.strip_prefix('.') {
+                Some(label) => match symbols {
+                    Some(symbols) => {
+                        symbols.get(label).copied().ok_or_else(|| RizeError {
+                            type_: RizeErrorType::Assemble,
+                            message: format!("Symbol '.{}' not found.", label),
+                        })
+                    }
+                    None => Ok(0),
+                },
+                None => literal(raw)
+                    .map(|value| value as u16)
+                    .ok_or_else(|| invalid(raw, "a Number or '.label'")),
+            })
+            .collect(),
+        "byte" => {
+            let bytes = args
+                .iter()
+                .map(|raw| {
+                    literal(raw)
+                        .filter(|value| (-128..=255).contains(value))
+                        .map(|value| value as u8)
+                        .ok_or_else(|| {
+                            invalid(raw, "a Number from -128 to 255")
+                        })
+                })
+                .collect::<Result<Vec<u8>, RizeError>>()?;
+            Ok(bytes
+                .chunks(2)
+                .map(|pair| {
+                    u16::from_be_bytes([
+                        pair[0],
+                        pair.get(1).copied().unwrap_or(0),
+                    ])
+                })
+                .collect())
+        }
+        "string" => {
+            let [raw] = args[..] else {
+                return Err(invalid(&args.join(" "), "a single \"String\""));
+            };
+            let text = raw
+                .strip_prefix('"')
+                .and_then(|raw| raw.strip_suffix('"'))
+                .and_then(unescape)
+                .ok_or_else(|| {
+                    invalid(raw, "a \"String\" of ASCII Characters")
+                })?;
+            Ok(text.iter().map(|c| *c as u16).chain([0]).collect())
+        }
+        "zero" => {
+            let [raw] = args[..] else {
+                return Err(invalid(&args.join(" "), "a single Count"));
+            };
+            let count = literal(raw)
+                .filter(|count| *count >= 0)
+                .ok_or_else(|| invalid(raw, "a positive Count"))?;
+            Ok(vec![0; count as usize])
+        }
+        _ => Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!("Unknown Directive '.{}'.", name),
+        }),
+    }
+}
+
+/// A Letter, followed by Letters, Digits or '_'.
+pub(super) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn literal(raw: &str) -> Option<i32> {
+    parse_literal(raw.strip_prefix('$').unwrap_or(raw))
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+/// A File pulled in by `.include "lib/math.azm"`.
+pub(super) struct Include {
+    /// As written in the Directive, relative to [AZZEMBLY_DIR]
+    pub name: String,
+    /// Canonical Path, to recognize the same File under another Name
+    pub path: PathBuf,
+}
+
+/// The loaded Program itself, so its Includes can't include it again.
+/// None if `path` can't be resolved.
+pub(super) fn main_file(path: &Path) -> Option<Include> {
+    Some(Include {
+        name: path.file_name()?.to_string_lossy().to_string(),
+        path: path.canonicalize().ok()?,
+    })
+}
+
+/// Reads the File an `.include` Directive names.
+///
+/// ### Dev Metadata
+/// - Paths are always relative to [AZZEMBLY_DIR], not to the including File
+/// - `including` are the Files currently being included, outermost first,
+///   including one of them again would never end
+pub(super) fn read_include(
+    args: &[&str],
+    including: &[Include],
+) -> Result<(Include, String), RizeError> {
+    let [quoted] = args else {
+        return Err(error("Expected '.include \"path\"'."));
+    };
+    let Some(name) = quoted
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+        .filter(|name| !name.is_empty())
+    else {
+        return Err(error(format!(
+            "Expected a quoted Path like \"lib/math.azm\", got {}.",
+            quoted
+        )));
+    };
+
+    let path = Path::new(AZZEMBLY_DIR)
+        .join(name)
+        .canonicalize()
+        .map_err(|e| error(format!("Can't include '{}': {}.", name, e)))?;
+
+    if let Some(start) = including.iter().position(|file| file.path == path) {
+        let chain = including[start..]
+            .iter()
+            .map(|file| file.name.as_str())
+            .chain([name])
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(error(format!("Include Cycle: {}.", chain)));
+    }
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| error(format!("Can't include '{}': {}.", name, e)))?;
+
+    Ok((
+        Include {
+            name: name.to_string(),
+            path,
+        },
+        contents,
+    ))
+}
+
+fn error(message: impl Into<String>) -> RizeError {
+    RizeError {
+        type_: RizeErrorType::Assemble,
+        message: message.into(),
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// How deep Macros may use other Macros, which also catches Recursion.
+const MAX_MACRO_DEPTH: usize = 16;
+
+struct Macro {
+    params: Vec<String>,
+    /// Every Line between '.macro' and '.endm'
+    body: Vec<(SourceLocation, String)>,
+}
+
+#[derive(Default)]
+struct Preprocessor {
+    macros: HashMap<String, Macro>,
+    /// Files currently being included, outermost first
+    including: Vec<Include>,
+    lines: Vec<SourceLine>,
+}
+
+/// Pastes in every `.include "path"`, see [read_include],
+/// removes every `.macro name params ... .endm` Definition
+/// and replaces every Use of a Macro with its Body.
+///
+/// ### Dev Metadata
+/// - Parameters are replaced as whole Tokens, and as the Register in `[p+2]`
+/// - Macros can use Macros defined above them, also in included Files
+/// - expanded Lines keep the Location of the Macro's Use,
+///   so 'pc' still points at a Line the User wrote
+/// - `main` is the loaded Program's File, if it came from one,
+///   its Lines get its Name just like included Lines do
+pub(super) fn preprocess(
+    source: &str,
+    main: Option<Include>,
+) -> Result<Vec<SourceLine>, RizeError> {
+    let file = main.as_ref().map(|main| main.name.clone());
+    let mut preprocessor = Preprocessor {
+        including: main.into_iter().collect(),
+        ..Default::default()
+    };
+    preprocessor.expand_file(&file.unwrap_or_default(), source)?;
+    Ok(preprocessor.lines)
+}
+
+impl Preprocessor {
+    fn expand_file(
+        &mut self,
+        file: &str,
+        source: &str,
+    ) -> Result<(), RizeError> {
+        let mut defining: Option<(SourceLocation, String, Macro)> = None;
+
+        for (n, text) in source.lines().enumerate() {
+            let location = SourceLocation {
+                file: file.to_string(),
+                line: n + 1,
+            };
+            let tokens = split_tokens(strip_comment(text).trim());
+            let first = tokens.first().copied();
+
+            if let Some((start, name, mut definition)) = defining.take() {
+                match first {
+                    Some(".endm") => {
+                        self.macros.insert(name, definition);
+                    }
+                    Some(".macro" | ".include") => {
+                        return Err(at_location(
+                            &location,
+                            error(format!(
+                                "'{}' inside Macro '{}' from {}.",
+                                first.unwrap_or_default(),
+                                name,
+                                start
+                            )),
+                        ));
+                    }
+                    _ => {
+                        definition.body.push((location, text.to_string()));
+                        defining = Some((start, name, definition));
+                    }
+                }
+                continue;
+            }
+
+            match first {
+                Some(".macro") => {
+                    let (name, definition) = self
+                        .define_macro(&tokens[1..])
+                        .map_err(|e| at_location(&location, e))?;
+                    defining = Some((location, name, definition));
+                }
+                Some(".endm") => {
+                    return Err(at_location(
+                        &location,
+                        error("'.endm' without '.macro'."),
+                    ));
+                }
+                Some(".include") => {
+                    let (include, contents) =
+                        read_include(&tokens[1..], &self.including)
+                            .map_err(|e| at_location(&location, e))?;
+                    let name = include.name.clone();
+                    self.including.push(include);
+                    self.expand_file(&name, &contents)?;
+                    self.including.pop();
+                }
+                _ => self.expand_line(location, text, None, 0)?,
+            }
+        }
+
+        if let Some((start, name, _)) = defining {
+            return Err(at_location(
+                &start,
+                error(format!("Macro '{}' is missing its '.endm'.", name)),
+            ));
+        }
+        Ok(())
+    }
+
+    fn define_macro(
+        &self,
+        tokens: &[&str],
+    ) -> Result<(String, Macro), RizeError> {
+        let Some((name, params)) = tokens.split_first() else {
+            return Err(error("Expected '.macro name params'."));
+        };
+        if let Some(invalid) = [*name]
+            .iter()
+            .chain(params)
+            .find(|name| !is_identifier(name))
+        {
+            return Err(error(format!(
+                "Can't define '{}', Names start with a Letter, \
+                followed by Letters, Digits or '_'.",
+                invalid
+            )));
+        }
+        if name.parse::<OpCode>().is_ok() || self.macros.contains_key(*name) {
+            return Err(error(format!(
+                "Macro '{}' is already an OpCode or Macro.",
+                name
+            )));
+        }
+        if let Some(duplicate) = params
+            .iter()
+            .enumerate()
+            .find(|(i, param)| params[..*i].contains(param))
+            .map(|(_, param)| param)
+        {
+            return Err(error(format!(
+                "Macro '{}' has the Parameter '{}' twice.",
+                name, duplicate
+            )));
+        }
+
+        Ok((
+            name.to_string(),
+            Macro {
+                params: params.iter().map(|param| param.to_string()).collect(),
+                body: Vec::new(),
+            },
+        ))
+    }
+
+    fn expand_line(
+        &mut self,
+        location: SourceLocation,
+        text: &str,
+        expanded_from: Option<(String, SourceLocation)>,
+        depth: usize,
+    ) -> Result<(), RizeError> {
+        let here = SourceLine {
+            trace: SourceTrace {
+                location,
+                expanded_from,
+            },
+            text: text.to_string(),
+        };
+        let tokens = split_tokens(strip_comment(text).trim());
+        let Some((name, definition, args)) =
+            tokens.split_first().and_then(|(name, args)| {
+                Some((name, self.macros.get(*name)?, args))
+            })
+        else {
+            self.lines.push(here);
+            return Ok(());
+        };
+
+        if depth >= MAX_MACRO_DEPTH {
+            return Err(here.locate(error(format!(
+                "Macro '{}' is nested deeper than {} Levels.",
+                name, MAX_MACRO_DEPTH
+            ))));
+        }
+        if args.len() != definition.params.len() {
+            return Err(here.locate(error(format!(
+                "Macro '{}' takes {} arguments, got {}.",
+                name,
+                definition.params.len(),
+                args.len()
+            ))));
+        }
+
+        let expanded = definition
+            .body
+            .iter()
+            .map(|(body_location, body)| {
+                (
+                    body_location.clone(),
+                    substitute(body, &definition.params, args),
+                )
+            })
+            .collect::<Vec<_>>();
+        for (body_location, body) in expanded {
+            self.expand_line(
+                here.trace.location.clone(),
+                &body,
+                Some((name.to_string(), body_location)),
+                depth + 1,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn substitute(text: &str, params: &[String], args: &[&str]) -> String {
+    let replace = |token: &str| -> Option<String> {
+        params
+            .iter()
+            .position(|param| param == token)
+            .map(|i| args[i].to_string())
+    };
+
+    split_tokens(strip_comment(text).trim())
+        .into_iter()
+        .map(|token| {
+            if let Some(arg) = replace(token) {
+                return arg;
+            }
+            let Some(inner) = token
+                .strip_prefix('[')
+                .and_then(|token| token.strip_suffix(']'))
+            else {
+                return token.to_string();
+            };
+            let (register, offset) =
+                inner.split_at(inner.find(['+', '-']).unwrap_or(inner.len()));
+            match replace(register) {
+                Some(register) => format!("[{}{}]", register, offset),
+                None => token.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn error(message: impl Into<String>) -> RizeError {
+    RizeError {
+        type_: RizeErrorType::Assemble,
+        message: message.into(),
+    }
+}
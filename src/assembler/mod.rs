@@ -0,0 +1,1116 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::*;
+
+mod directives;
+use directives::*;
+
+mod includes;
+use includes::*;
+
+mod macros;
+use macros::*;
+
+/// Machine Code produced by [assemble], ready to be loaded at [PROGRAM_START].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Assembly {
+    pub words: Vec<u16>,
+    /// Symbol Name -> Address
+    pub symbols: HashMap<String, u16>,
+    /// Instruction Address -> where it was written
+    pub source_lines: HashMap<u16, SourceTrace>,
+    pub metadata: Metadata,
+    /// First Address after the last Instruction, Data may follow it
+    pub end: u16,
+}
+
+/// Settings a Program declares in its Header, as `#! key: value` Lines.
+///
+/// ### Dev Metadata
+/// | Key    | Value                               | Default                       |
+/// | ------ | ----------------------------------- | ----------------------------- |
+/// | gprs   | Amount of GPRs, 1..=[MAX_GPRS]      | [N_GENERAL_PURPOSE_REGISTERS] |
+/// | memory | Memory Size in Words, see [Memory]  | [MEMORY_SIZE_WORDS]           |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub gprs: usize,
+    pub memory: usize,
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Self {
+            gprs: N_GENERAL_PURPOSE_REGISTERS,
+            memory: MEMORY_SIZE_WORDS,
+        }
+    }
+}
+
+/// Where a Line of azzembly Source was written.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Included File, relative to [AZZEMBLY_DIR],
+    /// for the loaded Program its File Name, empty if it has none
+    pub file: String,
+    /// Starting at 1
+    pub line: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.file.is_empty() {
+            return write!(f, "Line {}", self.line);
+        }
+        write!(f, "Line {} of '{}'", self.line, self.file)
+    }
+}
+
+/// Where a Line came from, including the Macro that produced it.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SourceTrace {
+    /// Expanded Macros keep the Location they were used at
+    pub location: SourceLocation,
+    /// Name of the Macro, and where the Line is in its Body
+    pub expanded_from: Option<(String, SourceLocation)>,
+}
+
+impl fmt::Display for SourceTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((name, body)) = &self.expanded_from else {
+            return write!(f, "{}", self.location);
+        };
+        write!(f, "{} (Macro '{}', {})", self.location, name, body)
+    }
+}
+
+/// A Line of azzembly Source, after Includes and Macros have been expanded.
+struct SourceLine {
+    trace: SourceTrace,
+    text: String,
+}
+
+impl SourceLine {
+    /// Prefixes an Error with where it happened.
+    fn locate(&self, e: RizeError) -> RizeError {
+        RizeError {
+            type_: e.type_,
+            message: format!("{}: {}", self.trace, e.message),
+        }
+    }
+}
+
+/// A single meaningful Line of azzembly Source.
+enum Statement<'a> {
+    Label(&'a str),
+    Instruction(Vec<&'a str>),
+    /// Name without the '.', and its Arguments
+    Directive(&'a str, Vec<&'a str>),
+}
+
+/// Translates azzembly Source into encoded Instructions.
+///
+/// ### Dev Metadata
+/// 0) expand Includes and Macros, see [preprocess]
+/// 1) First Pass: assign an Address to every Instruction, Data and Symbol
+/// 2) Second Pass: parse and encode every Instruction and Data Directive,
+///    replacing Symbols with their Address
+/// - Data sits where it was written, so it belongs behind a 'HALT' or 'JMP'.
+/// - both Passes track the last Global Label, see [define_label]
+pub fn assemble(source: &str) -> Result<Assembly, RizeError> {
+    assemble_with_main(source, None)
+}
+
+/// Like [assemble], for the Source of the File at `path`,
+/// so its Includes can't include the Program itself again,
+/// and its Lines are located in that File.
+pub fn assemble_file(source: &str, path: &Path) -> Result<Assembly, RizeError> {
+    assemble_with_main(source, main_file(path))
+}
+
+fn assemble_with_main(
+    source: &str,
+    main: Option<Include>,
+) -> Result<Assembly, RizeError> {
+    let mut assembly = Assembly {
+        metadata: parse_metadata(source)?,
+        ..Default::default()
+    };
+
+    let lines = preprocess(source, main)?;
+
+    // First Pass
+    let mut definitions = Definitions::default();
+    let mut defined_at: HashMap<String, &SourceLocation> = HashMap::new();
+    let mut scope = String::new();
+    let mut address = PROGRAM_START;
+    for (line, statement) in statements(&lines) {
+        match statement {
+            Statement::Label(name) => {
+                let name = define_label(name, &mut scope)
+                    .map_err(|e| line.locate(e))?;
+                if let Some(first) = defined_at.get(&name) {
+                    return Err(line.locate(RizeError {
+                        type_: RizeErrorType::Assemble,
+                        message: format!(
+                            "Label '.{}' is already defined at {}.",
+                            name, first
+                        ),
+                    }));
+                }
+                defined_at.insert(name.clone(), &line.trace.location);
+                assembly.symbols.insert(name, address);
+            }
+            Statement::Instruction(_) => {
+                assembly.source_lines.insert(address, line.trace.clone());
+                address = next_address(address, INSTRUCTION_WORDS)
+                    .map_err(|e| line.locate(e))?;
+                assembly.end = address;
+            }
+            Statement::Directive(name @ ("equ" | "alias"), args) => {
+                definitions
+                    .define(name, &args)
+                    .map_err(|e| line.locate(e))?;
+            }
+            Statement::Directive(name, args) => {
+                let size =
+                    assemble_data(name, &args, &scope, &definitions, None)
+                        .map_err(|e| line.locate(e))?
+                        .len();
+                address =
+                    next_address(address, size).map_err(|e| line.locate(e))?;
+            }
+        }
+    }
+
+    // Second Pass
+    let mut scope = String::new();
+    for (line, statement) in statements(&lines) {
+        let words = match statement {
+            Statement::Label(name) => {
+                // Already validated in the First Pass
+                if !name.starts_with('.') {
+                    scope = name.to_string();
+                }
+                continue;
+            }
+            Statement::Directive("equ" | "alias", _) => continue,
+            Statement::Instruction(parts) => assemble_instruction(
+                &parts,
+                &scope,
+                &assembly.symbols,
+                &definitions,
+                &assembly.metadata,
+            )
+            .map(Vec::from),
+            Statement::Directive(name, args) => assemble_data(
+                name,
+                &args,
+                &scope,
+                &definitions,
+                Some(&assembly.symbols),
+            ),
+        }
+        .map_err(|e| line.locate(e))?;
+        assembly.words.extend_from_slice(&words);
+    }
+
+    Ok(assembly)
+}
+
+/// Reads the Header, which are all Lines before the first Statement.
+/// Only `#!` Lines in it are Metadata, everything else is a Comment.
+fn parse_metadata(source: &str) -> Result<Metadata, RizeError> {
+    let mut metadata = Metadata::default();
+
+    for (n, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with('#') {
+            break;
+        }
+        let Some(entry) = line.strip_prefix("#!") else {
+            continue;
+        };
+
+        let invalid_entry = || RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!(
+                "Line {}: Invalid Metadata '{}', expected '#! key: value'.",
+                n + 1,
+                entry.trim()
+            ),
+        };
+        let (key, value) = entry.split_once(':').ok_or_else(invalid_entry)?;
+
+        let key = key.trim().to_ascii_lowercase();
+        match key.as_str() {
+            "gprs" => {
+                metadata.gprs = parse_in_range(&key, value, 1, MAX_GPRS)
+                    .map_err(|e| at_line(n + 1, e))?;
+            }
+            "memory" => {
+                metadata.memory = parse_in_range(
+                    &key,
+                    value,
+                    MIN_MEMORY_SIZE,
+                    MAX_MEMORY_SIZE,
+                )
+                .map_err(|e| at_line(n + 1, e))?;
+            }
+            _ => return Err(invalid_entry()),
+        }
+    }
+
+    Ok(metadata)
+}
+
+fn parse_in_range(
+    key: &str,
+    value: &str,
+    min: usize,
+    max: usize,
+) -> Result<usize, RizeError> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or_else(|| RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!(
+                "'{}' has to be between {} and {}.",
+                key, min, max
+            ),
+        })
+}
+
+/// ### Parsing Rules
+///
+/// Everything after a '#' is a Comment, see [strip_comment].
+///
+/// 1) if starts with '.'       -> Directive, if it names a [DIRECTIVES] Entry
+/// 2) if starts with '.'       -> Label, see [define_label]
+/// 3) otherwise                -> Instruction, split by Whitespace
+fn statements(
+    lines: &[SourceLine],
+) -> impl Iterator<Item = (&SourceLine, Statement<'_>)> {
+    lines.iter().filter_map(|line| {
+        let code = strip_comment(&line.text).trim();
+        if code.is_empty() {
+            return None;
+        }
+
+        let tokens = split_tokens(code);
+        if let Some((name, args)) = tokens
+            .split_first()
+            .and_then(|(first, args)| Some((first.strip_prefix('.')?, args)))
+            .filter(|(name, _)| DIRECTIVES.contains(name))
+        {
+            return Some((line, Statement::Directive(name, args.to_vec())));
+        }
+
+        if let Some(name) = code.strip_prefix('.') {
+            return Some((line, Statement::Label(name)));
+        }
+
+        Some((line, Statement::Instruction(tokens)))
+    })
+}
+
+/// Cuts off the Comment, which starts at the first '#' outside of Quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quotes = Quotes::default();
+    for (i, c) in line.char_indices() {
+        if !quotes.step(c) && c == '#' {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+/// Splits by Whitespace, but keeps quoted Text like `' '` in one Token.
+fn split_tokens(code: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut quotes = Quotes::default();
+    let mut start = None;
+    for (i, c) in code.char_indices() {
+        if quotes.step(c) || !c.is_whitespace() {
+            start.get_or_insert(i);
+            continue;
+        }
+        if let Some(s) = start.take() {
+            tokens.push(&code[s..i]);
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(&code[s..]);
+    }
+    tokens
+}
+
+/// Tracks whether a Scan over a Line is inside of Quotes.
+#[derive(Default)]
+struct Quotes {
+    open: Option<char>,
+    escaped: bool,
+}
+
+impl Quotes {
+    /// Returns true while `c` is quoted Text, the Quotes themselves included.
+    fn step(&mut self, c: char) -> bool {
+        match self.open {
+            Some(_) if self.escaped => self.escaped = false,
+            Some(_) if c == '\\' => self.escaped = true,
+            Some(q) if c == q => self.open = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => self.open = Some(c),
+            None => return false,
+        }
+        true
+    }
+}
+
+/// `name` or a Local Label's full Name `global..local`,
+/// both Parts being an Identifier, see [is_identifier].
+fn is_valid_symbol(name: &str) -> bool {
+    match name.split_once("..") {
+        Some((global, local)) => is_identifier(global) && is_identifier(local),
+        None => is_identifier(name),
+    }
+}
+
+/// Returns the full Name of a Label Line (without its leading '.').
+///
+/// ### Dev Metadata
+/// - Global Labels (`.loop`) start a new Scope
+/// - Local Labels (`..inner`) belong to the Global Label above them,
+///   and are stored as `loop..inner`
+/// - inside its Scope, `..inner` refers to it, everywhere else `.loop..inner`
+fn define_label(name: &str, scope: &mut String) -> Result<String, RizeError> {
+    let error = |message: String| RizeError {
+        type_: RizeErrorType::Assemble,
+        message,
+    };
+
+    let local = name.strip_prefix('.');
+    if !is_identifier(local.unwrap_or(name)) {
+        return Err(error(format!(
+            "Invalid Label '.{}', Labels start with a Letter, \
+            followed by Letters, Digits or '_'.",
+            name
+        )));
+    }
+
+    match local {
+        Some(local) if scope.is_empty() => Err(error(format!(
+            "Local Label '..{}' needs a Global Label above it.",
+            local
+        ))),
+        Some(local) => Ok(format!("{}..{}", scope, local)),
+        None => {
+            *scope = name.to_string();
+            Ok(name.to_string())
+        }
+    }
+}
+
+/// Turns `..inner` and `$..inner` into the full Name of that Local Label,
+/// see [define_label].
+fn scope_local_label(raw: &str, scope: &str) -> String {
+    let (prefix, rest) = match raw.strip_prefix('$') {
+        Some(rest) => ("$", rest),
+        None => ("", raw),
+    };
+    match rest.strip_prefix("..") {
+        Some(local) if !scope.is_empty() => {
+            format!("{}.{}..{}", prefix, scope, local)
+        }
+        _ => raw.to_string(),
+    }
+}
+
+fn next_address(address: u16, words: usize) -> Result<u16, RizeError> {
+    u16::try_from(words)
+        .ok()
+        .and_then(|words| address.checked_add(words))
+        .ok_or_else(|| RizeError {
+            type_: RizeErrorType::Assemble,
+            message: "Program does not fit into the 16-bit address space."
+                .to_string(),
+        })
+}
+
+fn assemble_instruction(
+    parts: &[&str],
+    scope: &str,
+    symbols: &HashMap<String, u16>,
+    definitions: &Definitions,
+    metadata: &Metadata,
+) -> Result<[u16; INSTRUCTION_WORDS], RizeError> {
+    let expanded: Vec<String> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| match i {
+            0 => part.to_string(),
+            _ => definitions.expand(&scope_local_label(part, scope)),
+        })
+        .collect();
+    let parts: Vec<&str> = expanded.iter().map(String::as_str).collect();
+
+    let raw_opcode = parts[0];
+    let opcode = OpCode::from_str(raw_opcode).map_err(|_| RizeError {
+        type_: RizeErrorType::Assemble,
+        message: format!("Unknown OpCode '{}'.", raw_opcode),
+    })?;
+
+    if parts.len() > 4 {
+        return Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!(
+                "{} takes at most 3 arguments, got {}.",
+                raw_opcode,
+                parts.len() - 1
+            ),
+        });
+    }
+
+    let mut args: [ArgType; 3] = Default::default();
+    for (arg, raw) in args.iter_mut().zip(parts.iter().skip(1)) {
+        *arg = resolve_arg(raw, symbols)?;
+        check_gpr_declared(arg, metadata)?;
+    }
+    check_literals_fit(opcode, &parts, &args)?;
+
+    if opcode.is_branch() && !matches!(args[0], ArgType::MemAddr(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!(
+                "{} needs a '.label' or Address as its Jump Target.",
+                raw_opcode
+            ),
+        });
+    }
+
+    encode_instruction(opcode, [&args[0], &args[1], &args[2]])
+}
+
+fn check_gpr_declared(
+    arg: &ArgType,
+    metadata: &Metadata,
+) -> Result<(), RizeError> {
+    let (ArgType::Register(name) | ArgType::Indirect(name, _)) = arg else {
+        return Ok(());
+    };
+    let name = name.to_ascii_lowercase();
+    let mut chars = name.chars();
+    if chars.next() != Some('g') {
+        return Ok(());
+    }
+    let Some(letter) = chars.next().filter(char::is_ascii_lowercase) else {
+        return Ok(());
+    };
+
+    if ((letter as u8 - b'a') as usize) < metadata.gprs {
+        return Ok(());
+    }
+    Err(RizeError {
+        type_: RizeErrorType::Assemble,
+        message: format!(
+            "Register '{}' is beyond the {} GPRs this Program declared.",
+            name, metadata.gprs
+        ),
+    })
+}
+
+/// Immediates that get combined with ARG1 have to fit into its Section.
+/// Negative Literals only need to fit as Two's Complement.
+fn check_literals_fit(
+    opcode: OpCode,
+    parts: &[&str],
+    args: &[ArgType; 3],
+) -> Result<(), RizeError> {
+    if !matches!(
+        opcode,
+        OpCode::MOV
+            | OpCode::ADD
+            | OpCode::SUB
+            | OpCode::ADC
+            | OpCode::SBB
+            | OpCode::MUL
+            | OpCode::DIV
+            | OpCode::MOD
+            | OpCode::IMUL
+            | OpCode::IDIV
+            | OpCode::AND
+            | OpCode::OR
+            | OpCode::XOR
+            | OpCode::CMP
+            | OpCode::TEST
+    ) {
+        return Ok(());
+    }
+    let ArgType::Register(destination) = &args[0] else {
+        return Ok(());
+    };
+    let width = register_width(destination) as u32;
+
+    for (arg, raw) in args.iter().zip(parts.iter().skip(1)).skip(1) {
+        if !matches!(arg, ArgType::Immediate(_)) {
+            continue;
+        }
+        let Some(value) = parse_literal(raw.strip_prefix('$').unwrap_or(raw))
+        else {
+            continue;
+        };
+        let fits = match value < 0 {
+            true => value >= -(1 << (width - 1)),
+            false => value < 1 << width,
+        };
+        if !fits {
+            return Err(RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!(
+                    "Literal '{}' does not fit into the {} Bits of '{}'.",
+                    raw, width, destination
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn register_width(name: &str) -> usize {
+    let name = name.to_ascii_lowercase();
+    let flags = [
+        FLAG_ZERO,
+        FLAG_CARRY,
+        FLAG_OVERFLOW,
+        FLAG_NEGATIVE,
+        FLAG_INTERRUPT,
+    ];
+    if flags.contains(&name.as_str())
+        || (name.starts_with(FLAG_REGISTER) && name.len() > FLAG_REGISTER.len())
+    {
+        return 1;
+    }
+    match name.strip_prefix('g').map(|gpr| gpr.chars().nth(1)) {
+        Some(section) => section_width(section.unwrap_or('a')),
+        None => CPU_BITTAGE,
+    }
+}
+
+fn resolve_arg(
+    raw: &str,
+    symbols: &HashMap<String, u16>,
+) -> Result<ArgType, RizeError> {
+    if let Some(name) = raw.strip_prefix("$.") {
+        return symbols
+            .get(name)
+            .map(|address| ArgType::Immediate(*address))
+            .ok_or_else(|| RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!("Symbol '.{}' not found.", name),
+            });
+    }
+    match parse_arg(raw) {
+        ArgType::Symbol(name) => symbols
+            .get(&name)
+            .map(|address| ArgType::MemAddr(*address))
+            .ok_or_else(|| RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!("Symbol '.{}' not found.", name),
+            }),
+        ArgType::Error => Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!("Invalid argument '{}'.", raw),
+        }),
+        arg => Ok(arg),
+    }
+}
+
+fn at_line(line_number: usize, e: RizeError) -> RizeError {
+    RizeError {
+        type_: e.type_,
+        message: format!("Line {}: {}", line_number, e.message),
+    }
+}
+
+fn at_location(location: &SourceLocation, e: RizeError) -> RizeError {
+    RizeError {
+        type_: e.type_,
+        message: format!("{}: {}", location, e.message),
+    }
+}
+
+/// ### Parsing Rules
+///
+/// Rules apply in Order, returning the first match.
+///
+/// 0) if starts with '$'       -> Immediate, the Literal after it may be Hex
+/// 1) if only characters       -> Register
+/// 2) if starts with '0x'      -> MemAddr
+/// 3) if is a Literal          -> Immediate, see [parse_literal]
+/// 4) if starts with '.'       -> Symbol
+/// 5) if wrapped in '[]'       -> Indirect, `[gaa]`, `[gaa+4]` or `[gaa-4]`
+pub fn parse_arg(arg: &str) -> ArgType {
+    if arg.is_empty() {
+        return ArgType::None;
+    }
+
+    // Rule 0: Immediates that would otherwise be an Address
+    if let Some(literal) = arg.strip_prefix('$') {
+        return match parse_literal(literal) {
+            Some(imm) => ArgType::Immediate(imm as u16),
+            None => ArgType::Error,
+        };
+    }
+
+    // Rule 1: Register
+    if arg.chars().all(|c| c.is_alphabetic()) {
+        return ArgType::Register(arg.to_string());
+    }
+
+    // Rule 2: Memory Address (Hexadecimal)
+    if let Some(hex_val) = arg.strip_prefix("0x") {
+        if let Ok(addr) = u16::from_str_radix(hex_val, 16) {
+            return ArgType::MemAddr(addr);
+        }
+        return ArgType::Error;
+    }
+
+    // Rule 3: Immediate
+    if let Some(imm) = parse_literal(arg) {
+        return ArgType::Immediate(imm as u16);
+    }
+
+    // Rule 4: Symbol
+    if let Some(symbol_name) = arg.strip_prefix('.') {
+        if is_valid_symbol(symbol_name) {
+            return ArgType::Symbol(symbol_name.to_string());
+        }
+        // If it starts with '.' but isn't a valid symbol format
+        return ArgType::Error;
+    }
+
+    // Rule 5: Indirect
+    if let Some(inner) =
+        arg.strip_prefix('[').and_then(|arg| arg.strip_suffix(']'))
+    {
+        return parse_indirect(inner).unwrap_or(ArgType::Error);
+    }
+
+    // Default/Error if none of the above match
+    ArgType::Error
+}
+
+fn parse_indirect(inner: &str) -> Option<ArgType> {
+    let (register, offset) = match inner.find(['+', '-']) {
+        Some(split) => {
+            let (register, offset) = inner.split_at(split);
+            (register, offset.parse::<i8>().ok()?)
+        }
+        None => (inner, 0),
+    };
+
+    if register.is_empty() || !register.chars().all(char::is_alphabetic) {
+        return None;
+    }
+    Some(ArgType::Indirect(register.to_string(), offset))
+}
+
+/// Reads a Number the way it was written, before it is stored as a u16.
+///
+/// ### Dev Metadata
+/// | Literal  | Example    | Range             |
+/// | -------- | ---------- | ----------------- |
+/// | Decimal  | `42`       | 0..=65535         |
+/// | Negative | `-42`      | -32768..=-1       |
+/// | Hex      | `0x2A`     | 0x0..=0xFFFF      |
+/// | Binary   | `0b101010` | 16 Bits at most   |
+/// | ASCII    | `'*'`      | `'\n'`, `'\t'`, `'\0'`, `'\\'`, `'\''` |
+///
+/// - Negative Numbers are stored as Two's Complement
+/// - Hex is only an Immediate with a leading '$', see [parse_arg]
+pub fn parse_literal(literal: &str) -> Option<i32> {
+    if let Some(hex) = literal.strip_prefix("0x") {
+        return u16::from_str_radix(hex, 16).ok().map(i32::from);
+    }
+    if let Some(binary) = literal.strip_prefix("0b") {
+        return u16::from_str_radix(binary, 2).ok().map(i32::from);
+    }
+    if let Some(c) = literal
+        .strip_prefix('\'')
+        .and_then(|literal| literal.strip_suffix('\''))
+    {
+        return parse_char(c).map(i32::from);
+    }
+    if let Some(magnitude) = literal.strip_prefix('-') {
+        return magnitude
+            .parse::<u16>()
+            .ok()
+            .filter(|magnitude| *magnitude <= 0x8000)
+            .map(|magnitude| -i32::from(magnitude));
+    }
+    if !literal.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    literal.parse::<u16>().ok().map(i32::from)
+}
+
+fn parse_char(c: &str) -> Option<u8> {
+    match unescape(c)?[..] {
+        [c] => Some(c),
+        _ => None,
+    }
+}
+
+/// Replaces `\n`, `\t`, `\0`, `\\`, `\'` and `\"`, only ASCII is allowed.
+pub(crate) fn unescape(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '0' => '\0',
+                escaped @ ('\\' | '\'' | '"') => escaped,
+                _ => return None,
+            },
+            c => c,
+        };
+        if !c.is_ascii() {
+            return None;
+        }
+        bytes.push(c as u8);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(source: &str) -> Vec<u16> {
+        assemble(source).expect("assembles").words
+    }
+
+    fn error(source: &str) -> String {
+        match assemble(source) {
+            Ok(_) => panic!("expected an Error for {:?}", source),
+            Err(e) => e.message,
+        }
+    }
+
+    #[test]
+    fn resolves_labels_in_a_second_pass() {
+        let assembly =
+            assemble("JMP .end\nNOP\n.end\nHALT").expect("assembles");
+        let end = 2 * INSTRUCTION_WORDS as u16;
+        assert_eq!(assembly.symbols.get("end"), Some(&end));
+        assert_eq!(assembly.words.len(), 3 * INSTRUCTION_WORDS);
+        assert_eq!(
+            assembly.words[..INSTRUCTION_WORDS],
+            encode_instruction(
+                OpCode::JMP,
+                [&ArgType::MemAddr(end), &ArgType::None, &ArgType::None]
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_the_failing_line() {
+        assert_eq!(error("NOP\nFOO GAA"), "Line 2: Unknown OpCode 'FOO'.");
+        assert!(error("JMP .nowhere").contains("nowhere"));
+    }
+
+    #[test]
+    fn ignores_blank_lines_and_comments() {
+        assert_eq!(words("\n# Comment\nNOP # trailing\n\n"), words("NOP"));
+    }
+
+    #[test]
+    fn reads_the_gprs_header() {
+        let assembly =
+            assemble("# A Program\n#! gprs: 3\nMOV GCA 1").expect("assembles");
+        assert_eq!(assembly.metadata.gprs, 3);
+        assert_eq!(
+            assemble("NOP").unwrap().metadata.gprs,
+            N_GENERAL_PURPOSE_REGISTERS
+        );
+
+        assert_eq!(
+            error("#! gprs: 3\nMOV GDA 1"),
+            "Line 2: Register 'gda' is beyond the 3 GPRs this Program declared."
+        );
+        assert_eq!(
+            error("#! gprs: 0"),
+            "Line 1: 'gprs' has to be between 1 and 26."
+        );
+        assert!(error("#! colour: red").contains("Invalid Metadata"));
+    }
+
+    #[test]
+    fn reads_the_memory_header() {
+        let assembly = assemble("#! memory: 4096\nNOP").expect("assembles");
+        assert_eq!(assembly.metadata.memory, 4096);
+        assert_eq!(assemble("NOP").unwrap().metadata.memory, MEMORY_SIZE_WORDS);
+        assert!(error("#! memory: 256").contains("between 512 and 65280"));
+        assert!(error("#! memory: 65281").contains("between 512 and 65280"));
+    }
+
+    #[test]
+    fn parses_every_literal_form() {
+        assert_eq!(parse_literal("42"), Some(42));
+        assert_eq!(parse_literal("-42"), Some(-42));
+        assert_eq!(parse_literal("-32768"), Some(-32768));
+        assert_eq!(parse_literal("-32769"), None);
+        assert_eq!(parse_literal("0x2A"), Some(42));
+        assert_eq!(parse_literal("0b101010"), Some(42));
+        assert_eq!(parse_literal("'*'"), Some(42));
+        assert_eq!(parse_literal(r"'\n'"), Some(10));
+        assert_eq!(parse_literal(r"'\''"), Some(39));
+        assert_eq!(parse_literal("65536"), None);
+        assert_eq!(parse_literal("0x10000"), None);
+        assert_eq!(parse_literal("'ab'"), None);
+        assert_eq!(parse_literal("4a"), None);
+    }
+
+    #[test]
+    fn literals_have_to_fit_their_register() {
+        assert_eq!(
+            error("MOV GAB 256"),
+            "Line 1: Literal '256' does not fit into the 8 Bits of 'GAB'."
+        );
+        assert!(error("MOV GAB -129").contains("does not fit"));
+        assert!(error("ADD GAC 16").contains("does not fit into the 4 Bits"));
+        assert!(error("MOV GAA 65536").contains("Invalid argument"));
+        assert!(assemble("MOV GAB -128").is_ok());
+        assert!(assemble("MOV GAB 255").is_ok());
+    }
+
+    #[test]
+    fn dollar_marks_hex_immediates() {
+        assert_eq!(words("MOV GAB $0xFF"), words("MOV GAB 255"));
+        assert_eq!(words("MOV GAB $0b11"), words("MOV GAB 3"));
+        assert_eq!(words("MOV GAB 'A'"), words("MOV GAB 65"));
+        assert_ne!(words("MOV GAA 0xFF"), words("MOV GAA $0xFF"));
+        assert!(error("MOV GAA $0xZZ").contains("Invalid argument"));
+    }
+
+    #[test]
+    fn hash_always_starts_a_comment() {
+        assert_eq!(words("MOV GAA 5 #1st value"), words("MOV GAA 5"));
+        assert_eq!(words("MOV GAA $0x10#hex"), words("MOV GAA 16"));
+        assert_eq!(words("MOV GAB '#'"), words("MOV GAB 35"));
+    }
+
+    #[test]
+    fn data_directives() {
+        assert_eq!(words(".word 1 -1 $0xBEEF 'A'"), [1, 0xFFFF, 0xBEEF, 65]);
+        assert_eq!(words(".byte 1 2 3"), [0x0102, 0x0300]);
+        assert_eq!(words(".byte -1 255"), [0xFFFF]);
+        assert_eq!(words(r#".string "Hi\n""#), [72, 105, 10, 0]);
+        assert_eq!(words(".zero 3"), [0, 0, 0]);
+    }
+
+    #[test]
+    fn data_directives_take_labels_and_move_later_labels() {
+        let assembly =
+            assemble(".table\n.word .end .table\n.zero 2\n.end\nHALT")
+                .expect("assembles");
+        assert_eq!(assembly.symbols.get("end"), Some(&4));
+        assert_eq!(assembly.words[..2], [4, 0]);
+    }
+
+    #[test]
+    fn data_directive_errors() {
+        assert_eq!(
+            error(".byte 256"),
+            "Line 1: Invalid value '256' for '.byte', expected a Number from -128 to 255."
+        );
+        assert!(error(".word").contains("needs at least one value"));
+        assert!(error(".word x").contains("a Number or '.label'"));
+        assert!(
+            error(".string Hi").contains("a \"String\" of ASCII Characters")
+        );
+        assert!(error(".zero -1").contains("a positive Count"));
+        assert!(
+            error(".word .missing").contains("Symbol '.missing' not found.")
+        );
+    }
+
+    #[test]
+    fn equ_and_alias() {
+        assert_eq!(
+            words(".equ SIZE 32\n.alias x gce\n.alias p gba\nMOV x SIZE\nMOV [p+SIZE] SIZE"),
+            words("MOV GCE 32\nMOV [GBA+32] 32")
+        );
+        assert_eq!(words(".equ A 5\n.equ B A\nMOV GAA B"), words("MOV GAA 5"));
+        assert!(error(".alias x 5").contains("has to name a Register"));
+        assert!(error(".equ SIZE gaa").contains("has to be a Number"));
+        assert!(error(".equ gaa 1").contains("it is a Register"));
+        assert!(error(".equ A 1\n.equ A 2").contains("it is already defined"));
+        assert!(error(".equ SMALL 256\nMOV GAB SMALL").contains("does not fit"));
+    }
+
+    #[test]
+    fn constants_keep_the_kind_of_their_literal() {
+        assert_eq!(
+            words(".equ CONSOLE 0xFF02\nMOV CONSOLE 'A'"),
+            words("MOV 0xFF02 'A'")
+        );
+        assert_eq!(
+            words(".equ BUF 0x100\nMOV GAA BUF"),
+            words("MOV GAA 0x100")
+        );
+        assert_eq!(
+            words(".equ MASK $0xFF\nAND GAA MASK"),
+            words("AND GAA $0xFF")
+        );
+        assert_eq!(words(".equ BUF 0x100\n.word BUF"), [0x100]);
+        assert_eq!(
+            words(".alias p gaa\n.equ OFF 0x4\nMOV GBA [p+OFF]"),
+            words("MOV GBA [GAA+4]")
+        );
+    }
+
+    #[test]
+    fn word_takes_label_addresses_with_or_without_dollar() {
+        assert_eq!(words(".x\n.word $.x .x $5"), [0, 0, 5]);
+    }
+
+    #[test]
+    fn macros_expand_in_place() {
+        assert_eq!(
+            words(".macro inc2 r\nADD r 1\nADD r 1\n.endm\ninc2 GAA\nHALT"),
+            words("ADD GAA 1\nADD GAA 1\nHALT")
+        );
+        assert_eq!(
+            words(".macro load r a\nMOV r [a+2]\n.endm\nload GBA GAA"),
+            words("MOV GBA [GAA+2]")
+        );
+        assert_eq!(
+            words(".macro one r\nMOV r 1\n.endm\n.macro two r\none r\nADD r 1\n.endm\ntwo GCA"),
+            words("MOV GCA 1\nADD GCA 1")
+        );
+    }
+
+    #[test]
+    fn macro_errors() {
+        assert!(error(".macro inc2 r\nADD r 2\n.endm\ninc2 GAA GBA")
+            .contains("Macro 'inc2' takes 1 arguments, got 2."));
+        assert_eq!(
+            error(".macro a\nNOP"),
+            "Line 1: Macro 'a' is missing its '.endm'."
+        );
+        assert_eq!(error("NOP\n.endm"), "Line 2: '.endm' without '.macro'.");
+        assert!(error(".macro mov\n.endm")
+            .contains("is already an OpCode or Macro"));
+        assert!(error(".macro a x x\n.endm")
+            .contains("has the Parameter 'x' twice"));
+        assert!(error(".macro r\nr\n.endm\nr")
+            .contains("nested deeper than 16 Levels"));
+        assert!(error(".macro x\n.macro y\n.endm")
+            .contains("inside Macro 'x' from Line 1"));
+    }
+
+    #[test]
+    fn macro_lines_are_traced_to_their_use() {
+        assert_eq!(
+            error(".macro m\nNOP\nFOO GAA\n.endm\nNOP\nm"),
+            "Line 6 (Macro 'm', Line 3): Unknown OpCode 'FOO'."
+        );
+
+        let assembly =
+            assemble(".macro m\nNOP\nHALT\n.endm\nNOP\nm").expect("assembles");
+        let trace = &assembly.source_lines[&(2 * INSTRUCTION_WORDS as u16)];
+        assert_eq!(trace.location.line, 6);
+        assert_eq!(trace.to_string(), "Line 6 (Macro 'm', Line 3)");
+        assert_eq!(assembly.source_lines[&0].to_string(), "Line 5");
+    }
+
+    #[test]
+    fn includes() {
+        assert_eq!(
+            words(".include \"lib/console.azm\"\nputc 'A'\nHALT"),
+            words("MOV 0xFF02 'A'\nHALT")
+        );
+        assert_eq!(
+            error(".include \"lib/console.azm\"\nputc"),
+            "Line 2: Macro 'putc' takes 1 arguments, got 0."
+        );
+    }
+
+    #[test]
+    fn include_errors() {
+        let message = assemble_file(
+            ".include \"lib/console.azm\"",
+            Path::new("azzembly/lib/console.azm"),
+        )
+        .unwrap_err()
+        .message;
+        assert_eq!(
+            message,
+            "Line 1 of 'console.azm': Include Cycle: console.azm -> lib/console.azm."
+        );
+
+        assert!(error(".include \"lib/missing.azm\"")
+            .contains("Can't include 'lib/missing.azm'"));
+        assert!(error(".include lib/console.azm")
+            .contains("Expected a quoted Path"));
+        assert!(error(".macro m\n.include \"lib/console.azm\"\n.endm")
+            .contains("inside Macro 'm'"));
+    }
+
+    #[test]
+    fn lines_of_a_file_name_it() {
+        let assembly =
+            assemble_file("NOP\nHALT", Path::new("azzembly/Console.azm"))
+                .expect("assembles");
+        assert_eq!(
+            assembly.source_lines[&0].to_string(),
+            "Line 1 of 'Console.azm'"
+        );
+        assert_eq!(
+            assemble("NOP").unwrap().source_lines[&0].to_string(),
+            "Line 1"
+        );
+    }
+
+    #[test]
+    fn labels_take_digits_underscores_and_local_labels() {
+        let assembly = assemble(
+            ".loop_2\nNOP\n..inner\nJMP ..inner\n.end\n..inner\nJMP .loop_2..inner",
+        )
+        .expect("assembles");
+        assert_eq!(assembly.symbols.get("loop_2"), Some(&0));
+        assert_eq!(assembly.symbols.get("loop_2..inner"), Some(&4));
+        assert_eq!(assembly.symbols.get("end..inner"), Some(&8));
+        assert_eq!(
+            words(".a\n..x\nMOV GAA $..x"),
+            words(".a\n..x\nMOV GAA $.a..x")
+        );
+    }
+
+    #[test]
+    fn label_errors() {
+        assert_eq!(
+            error(".a\nNOP\n.a"),
+            "Line 3: Label '.a' is already defined at Line 1."
+        );
+        assert!(
+            error(".a\n..x\n..x").contains("Label '.a..x' is already defined")
+        );
+        assert!(error(".1x").contains("Invalid Label '.1x'"));
+        assert!(error(".a_b..c").contains("Invalid Label"));
+        assert_eq!(
+            error("..x"),
+            "Line 1: Local Label '..x' needs a Global Label above it."
+        );
+        assert!(error(".a\n.b\nJMP ..x").contains("not found"));
+    }
+}
@@ -1,20 +1,48 @@
 // CPU Bits
 pub const CPU_BITTAGE: usize = 16;
 pub const N_GENERAL_PURPOSE_REGISTERS: usize = 4;
-pub const INSTRUCTION_WIDTH: usize = 56;
+/// One GPR per Letter, 'ga' to 'gz'
+pub const MAX_GPRS: usize = 26;
+/// See [crate::Registers::get]
+pub const GPR_SECTIONS: [char; 7] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
+pub const INSTRUCTION_WIDTH: usize = 64;
+pub const INSTRUCTION_WORDS: usize = INSTRUCTION_WIDTH / CPU_BITTAGE;
 
 // Memory
-pub const MEMORY_SIZE_BYTES: usize = 2048;
+/// Default in Words, Programs can ask for a different Size in their Header
+pub const MEMORY_SIZE_WORDS: usize = 2048;
+pub const MIN_MEMORY_SIZE: usize = 512;
+/// Everything from [MMIO_START] upwards belongs to Devices
+pub const MAX_MEMORY_SIZE: usize = MMIO_START as usize;
+pub const PROGRAM_START: u16 = 0;
+pub const STACK_SIZE: usize = 256;
+pub const INTERRUPT_VECTORS: usize = 8;
+pub const TIMER_INTERRUPT: u8 = 0;
+
+// Memory-mapped I/O, above the last Memory Address
+pub const MMIO_START: u16 = 0xFF00;
+/// Status at +0, Data at +1, see [crate::Keyboard]
+pub const KEYBOARD_PORT: u16 = MMIO_START;
+/// Characters at +0, Numbers at +1, see [crate::Console]
+pub const CONSOLE_PORT: u16 = 0xFF02;
 
 // Other
 pub const DISPLAY_WIDTH: usize = 32;
 pub const DISPLAY_HEIGHT: usize = 32;
 pub const AZZEMBLY_DIR: &str = "azzembly/";
 pub const AUTOSTEP_LINES_PER_FRAME: usize = 20;
+pub const CONSOLE_VISIBLE_LINES: usize = 16;
 
 // Registers
 pub const PROGRAM_COUNTER: &str = "pc";
+pub const INSTRUCTION_REGISTER: &str = "ir";
+pub const MEMORY_ADDRESS_REGISTER: &str = "mar";
+pub const MEMORY_DATA_REGISTER: &str = "mdr";
+pub const STACK_POINTER: &str = "sp";
 pub const FLAG_ZERO: &str = "fz";
 pub const FLAG_NEGATIVE: &str = "fn";
 pub const FLAG_CARRY: &str = "fc";
 pub const FLAG_OVERFLOW: &str = "fo";
+pub const FLAG_INTERRUPT: &str = "fi";
+pub const FLAG_REGISTER: &str = "fr";
+pub const FLAG_REGISTER_BITS: usize = 8;
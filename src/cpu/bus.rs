@@ -0,0 +1,202 @@
+use crate::*;
+
+/// A Peripheral that claims [Device::size] consecutive Addresses on the [Bus].
+///
+/// ### Dev Metadata
+/// - `offset` is relative to the Address the Device was attached at
+/// - Reads and Writes only get `&self`, Devices that keep State
+///   use an `Arc<Mutex<_>>`, just like [Register] does,
+///   so a Frontend can hold on to a Handle and feed them
+pub trait Device: Send + Sync {
+    /// Shown in Errors and Frontends.
+    fn name(&self) -> &str;
+    fn size(&self) -> u16;
+    fn read(&self, offset: u16) -> Result<u16, RizeError>;
+    fn write(&self, offset: u16, value: u16) -> Result<(), RizeError>;
+}
+
+struct MappedDevice {
+    base: u16,
+    device: Box<dyn Device>,
+}
+
+impl MappedDevice {
+    fn last(&self) -> u16 {
+        self.base + (self.device.size() - 1)
+    }
+
+    fn offset_of(&self, address: u16) -> Option<u16> {
+        (self.base..=self.last())
+            .contains(&address)
+            .then(|| address - self.base)
+    }
+}
+
+/// Sits in front of [Memory] and hands every Access
+/// to the [Device] claiming that Address, if there is one.
+#[derive(Default)]
+pub struct Bus {
+    pub memory: Memory,
+    devices: Vec<MappedDevice>,
+}
+
+impl Bus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `device` to the Addresses starting at `base`.
+    /// Devices shadow [Memory], but can't overlap each other.
+    pub fn attach(
+        &mut self,
+        base: u16,
+        device: impl Device + 'static,
+    ) -> Result<(), RizeError> {
+        let size = device.size();
+        let Some(last) = size.checked_sub(1).and_then(|s| base.checked_add(s))
+        else {
+            return Err(RizeError {
+                type_: RizeErrorType::Device,
+                message: format!(
+                    "Device '{}' with size {} does not fit at 0x{:04X}.",
+                    device.name(),
+                    size,
+                    base
+                ),
+            });
+        };
+
+        if let Some(other) = self
+            .devices
+            .iter()
+            .find(|other| base <= other.last() && other.base <= last)
+        {
+            return Err(RizeError {
+                type_: RizeErrorType::Device,
+                message: format!(
+                    "Device '{}' overlaps '{}' at 0x{:04X}-0x{:04X}.",
+                    device.name(),
+                    other.device.name(),
+                    other.base,
+                    other.last()
+                ),
+            });
+        }
+
+        self.devices.push(MappedDevice {
+            base,
+            device: Box::new(device),
+        });
+        Ok(())
+    }
+
+    /// Name, first and last Address of every attached Device.
+    pub fn devices(&self) -> impl Iterator<Item = (&str, u16, u16)> {
+        self.devices
+            .iter()
+            .map(|mapped| (mapped.device.name(), mapped.base, mapped.last()))
+    }
+
+    pub fn read(&self, address: u16) -> Result<u16, RizeError> {
+        match self.find(address) {
+            Some((mapped, offset)) => mapped.device.read(offset),
+            None => self.memory.read(address),
+        }
+    }
+
+    pub fn write(&mut self, address: u16, data: u16) -> Result<(), RizeError> {
+        match self.find(address) {
+            Some((mapped, offset)) => mapped.device.write(offset, data),
+            None => self.memory.write(address, data),
+        }
+    }
+
+    fn find(&self, address: u16) -> Option<(&MappedDevice, u16)> {
+        self.devices.iter().find_map(|mapped| {
+            mapped.offset_of(address).map(|offset| (mapped, offset))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Remembers the last Word written to any of its Addresses.
+    #[derive(Default, Clone)]
+    struct Latch {
+        size: u16,
+        value: Arc<Mutex<u16>>,
+    }
+
+    impl Device for Latch {
+        fn name(&self) -> &str {
+            "latch"
+        }
+
+        fn size(&self) -> u16 {
+            self.size
+        }
+
+        fn read(&self, offset: u16) -> Result<u16, RizeError> {
+            Ok(*self.value.lock().unwrap() + offset)
+        }
+
+        fn write(&self, _offset: u16, value: u16) -> Result<(), RizeError> {
+            *self.value.lock().unwrap() = value;
+            Ok(())
+        }
+    }
+
+    fn latch(size: u16) -> Latch {
+        Latch {
+            size,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn devices_shadow_memory() {
+        let mut bus = Bus::new();
+        let device = latch(2);
+        bus.attach(0x100, device.clone()).unwrap();
+
+        bus.write(0x101, 40).unwrap();
+        assert_eq!(*device.value.lock().unwrap(), 40);
+        assert_eq!(bus.read(0x100), Ok(40));
+        assert_eq!(bus.read(0x101), Ok(41));
+        assert_eq!(bus.memory.read(0x101), Ok(0));
+
+        bus.write(0x102, 7).unwrap();
+        assert_eq!(bus.memory.read(0x102), Ok(7));
+        assert_eq!(
+            bus.devices().collect::<Vec<_>>(),
+            [("latch", 0x100, 0x101)]
+        );
+    }
+
+    #[test]
+    fn rejects_overlapping_devices() {
+        let mut bus = Bus::new();
+        bus.attach(0x100, latch(4)).unwrap();
+
+        let e = bus.attach(0x103, latch(2)).unwrap_err();
+        assert_eq!(
+            e.message,
+            "Device 'latch' overlaps 'latch' at 0x0100-0x0103."
+        );
+        assert!(bus.attach(0x0FF, latch(2)).is_err());
+        assert!(bus.attach(0x104, latch(2)).is_ok());
+        assert!(bus.attach(0x0FE, latch(2)).is_ok());
+    }
+
+    #[test]
+    fn rejects_devices_that_do_not_fit() {
+        let mut bus = Bus::new();
+        assert!(bus.attach(0x200, latch(0)).is_err());
+        assert!(bus.attach(0xFFFF, latch(2)).is_err());
+        assert!(bus.attach(0xFFFF, latch(1)).is_ok());
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::*;
+
+/// Writing this Character clears the Console.
+pub const CONSOLE_CLEAR: u16 = 12;
+
+/// Collects Text a Program prints, for a Frontend to show.
+///
+/// ### Dev Metadata
+/// | Offset | Write                                            |
+/// | ------ | ------------------------------------------------ |
+/// | 0      | ASCII Character, 10 -> Newline, 12 -> Clear      |
+/// | 1      | Number, printed in Decimal                       |
+///
+/// - only the last [Console::MAX_LINES] Lines are kept
+/// - Clones share the same Lines
+#[derive(Debug, Clone)]
+pub struct Console {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::from([String::new()]))),
+        }
+    }
+}
+
+impl Console {
+    pub const MAX_LINES: usize = 64;
+
+    pub fn print(&self, text: &str) -> Result<(), RizeError> {
+        let mut lines = self.lock()?;
+        for c in text.chars() {
+            if c == '\n' {
+                lines.push_back(String::new());
+                if lines.len() > Self::MAX_LINES {
+                    lines.pop_front();
+                }
+                continue;
+            }
+            if let Some(line) = lines.back_mut() {
+                line.push(c);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<(), RizeError> {
+        let mut lines = self.lock()?;
+        lines.clear();
+        lines.push_back(String::new());
+        Ok(())
+    }
+
+    /// The last `count` Lines, joined by Newlines.
+    pub fn tail(&self, count: usize) -> Result<String, RizeError> {
+        let lines = self.lock()?;
+        let skip = lines.len().saturating_sub(count);
+        Ok(lines
+            .iter()
+            .skip(skip)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, VecDeque<String>>, RizeError> {
+        self.lines.lock().map_err(|_| RizeError {
+            type_: RizeErrorType::Device,
+            message: "Failed to acquire lock for the Console".to_string(),
+        })
+    }
+}
+
+impl Device for Console {
+    fn name(&self) -> &str {
+        "console"
+    }
+
+    fn size(&self) -> u16 {
+        2
+    }
+
+    fn read(&self, _offset: u16) -> Result<u16, RizeError> {
+        Err(RizeError {
+            type_: RizeErrorType::Device,
+            message: "The Console is write-only.".to_string(),
+        })
+    }
+
+    fn write(&self, offset: u16, value: u16) -> Result<(), RizeError> {
+        if offset == 1 {
+            return self.print(&value.to_string());
+        }
+        if value == CONSOLE_CLEAR {
+            return self.clear();
+        }
+
+        let c = u8::try_from(value)
+            .ok()
+            .filter(|c| c.is_ascii())
+            .ok_or_else(|| RizeError {
+                type_: RizeErrorType::Device,
+                message: format!("{} is not an ASCII Character.", value),
+            })?;
+        self.print(&(c as char).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prints_characters_and_numbers() {
+        let console = Console::default();
+        for c in "Hi\n".bytes() {
+            console.write(0, c as u16).unwrap();
+        }
+        console.write(1, 42).unwrap();
+        assert_eq!(console.tail(2), Ok("Hi\n42".to_string()));
+        assert_eq!(console.tail(1), Ok("42".to_string()));
+
+        console.write(0, CONSOLE_CLEAR).unwrap();
+        assert_eq!(console.tail(2), Ok(String::new()));
+    }
+
+    #[test]
+    fn rejects_reads_and_non_ascii() {
+        let console = Console::default();
+        assert!(console.read(0).is_err());
+        assert!(console.write(0, 0x100).is_err());
+        assert!(console.write(0, 0xE9).is_err());
+    }
+
+    #[test]
+    fn keeps_only_the_last_lines() {
+        let console = Console::default();
+        for n in 0..Console::MAX_LINES + 10 {
+            console.print(&format!("{}\n", n)).unwrap();
+        }
+        let tail = console.tail(usize::MAX).unwrap();
+        assert_eq!(tail.lines().count(), Console::MAX_LINES - 1);
+        assert!(tail.ends_with(&format!("{}\n", Console::MAX_LINES + 9)));
+    }
+}
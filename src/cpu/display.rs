@@ -0,0 +1,78 @@
+use crate::*;
+
+/// ### Dev Metadata
+/// Each Row has 256 Columns,
+/// which each have 256 Pixels,
+/// which each have u8 Values for RGBA
+pub struct DisplayMemory {
+    pixels: [[[u8; 4]; DISPLAY_WIDTH]; DISPLAY_HEIGHT],
+}
+
+impl DisplayMemory {
+    pub fn init() -> Self {
+        let mut pixels = [[[0; 4]; DISPLAY_WIDTH]; DISPLAY_HEIGHT];
+        for (x, column) in pixels.iter_mut().enumerate() {
+            for (y, pixel) in column.iter_mut().enumerate() {
+                *pixel = [(x + 100) as u8, 100u8, (y + 100) as u8, 255u8];
+            }
+        }
+
+        Self { pixels }
+    }
+
+    pub fn reset(&mut self) {
+        self.pixels = Self::init().pixels;
+    }
+
+    pub fn set_pixel(
+        &mut self,
+        x: u8,
+        y: u8,
+        color: [u8; 4],
+    ) -> Result<(), RizeError> {
+        // Check X bounds
+        if (x as usize) >= DISPLAY_WIDTH {
+            return Err(RizeError {
+                type_: RizeErrorType::Display,
+                message: format!(
+                    "X coordinate {} out of bounds (width is {})",
+                    x, DISPLAY_WIDTH
+                ),
+            });
+        }
+        // Check Y bounds
+        if (y as usize) >= DISPLAY_HEIGHT {
+            return Err(RizeError {
+                type_: RizeErrorType::Display,
+                message: format!(
+                    "Y coordinate {} out of bounds (height is {})",
+                    y, DISPLAY_HEIGHT
+                ),
+            });
+        }
+
+        self.pixels[x as usize][y as usize] = color;
+        Ok(())
+    }
+
+    pub fn get_pixel(&self, x: u16, y: u16) -> Result<[u8; 4], RizeError> {
+        self.pixels
+            .get(x as usize)
+            .ok_or_else(|| RizeError {
+                type_: RizeErrorType::Display,
+                message: format!("X coordinate {} out of bounds", x),
+            })?
+            .get(y as usize)
+            .ok_or_else(|| RizeError {
+                type_: RizeErrorType::Display,
+                message: format!("Y coordinate {} out of bounds", y),
+            })
+            .copied()
+    }
+}
+
+impl Default for DisplayMemory {
+    fn default() -> Self {
+        Self::init()
+    }
+}
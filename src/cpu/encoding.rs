@@ -0,0 +1,367 @@
+use crate::*;
+
+const MODE_NONE: u16 = 0b00;
+const MODE_REGISTER: u16 = 0b01;
+const MODE_IMMEDIATE: u16 = 0b10;
+const MODE_MEM_ADDR: u16 = 0b11;
+const MODE_BITS: u16 = 2;
+const MODE_MASK: u16 = 0b11;
+/// Bits 7..6 of Word 0: which Argument (1..=3) is Indirect, 0 for none.
+const INDIRECT_SHIFT: u16 = 6;
+
+/// Registers that exist regardless of the amount of GPRs,
+/// encoded by their Index in this List.
+const FIXED_REGISTERS: &[&str] = &[
+    PROGRAM_COUNTER,
+    INSTRUCTION_REGISTER,
+    MEMORY_ADDRESS_REGISTER,
+    MEMORY_DATA_REGISTER,
+    FLAG_ZERO,
+    FLAG_CARRY,
+    FLAG_OVERFLOW,
+    FLAG_NEGATIVE,
+    STACK_POINTER,
+    FLAG_REGISTER,
+    FLAG_INTERRUPT,
+];
+const GPR_INDEX_OFFSET: u8 = 0x80;
+
+/// Encodes an Instruction into [INSTRUCTION_WORDS] Memory Words.
+///
+/// ### Dev Metadata
+/// | Word | Bits 15..8 | Bits 7..0                              |
+/// | ---- | ---------- | -------------------------------------- |
+/// | 0    | OpCode     | Operand Modes (--11 2233, 2 bits each) |
+/// | 1    | ARG1       |                                        |
+/// | 2    | ARG2       |                                        |
+/// | 3    | ARG3       |                                        |
+///
+/// - Modes: 00 -> None, 01 -> Register, 10 -> Immediate, 11 -> MemAddr
+/// - Bits 7..6 of Word 0 ('--'): Number of the Indirect Argument, 00 -> none
+///     - so at most one Argument per Instruction can be Indirect
+/// - Register Operands: High Byte -> Section ('a'..'g'), Low Byte -> Index
+///     - 'fr' Bits: High Byte -> Bit ('a'..'h'), Low Byte -> Index of 'fr'
+///     - Indirect: High Byte -> Offset (i8), Low Byte -> Index
+pub fn encode_instruction(
+    opcode: OpCode,
+    args: [&ArgType; 3],
+) -> Result<[u16; INSTRUCTION_WORDS], RizeError> {
+    let mut words = [0u16; INSTRUCTION_WORDS];
+    let mut modes: u16 = 0;
+
+    if args
+        .iter()
+        .filter(|arg| matches!(arg, ArgType::Indirect(..)))
+        .count()
+        > 1
+    {
+        return Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: "Only one Argument per Instruction can be Indirect."
+                .to_string(),
+        });
+    }
+
+    for (i, arg) in args.iter().enumerate() {
+        let (mode, field) = match arg {
+            ArgType::None => (MODE_NONE, 0),
+            ArgType::Register(name) => (MODE_REGISTER, encode_register(name)?),
+            ArgType::Indirect(name, offset) => {
+                modes |= (i as u16 + 1) << INDIRECT_SHIFT;
+                let index = encode_pointer_register(name)?;
+                (MODE_REGISTER, (*offset as u8 as u16) << 8 | index)
+            }
+            ArgType::Immediate(imm) => (MODE_IMMEDIATE, *imm),
+            ArgType::MemAddr(addr) => (MODE_MEM_ADDR, *addr),
+            ArgType::Symbol(sym) => {
+                return Err(RizeError {
+                    type_: RizeErrorType::Assemble,
+                    message: format!(
+                        "Symbol '.{}' has to be resolved before encoding.",
+                        sym
+                    ),
+                })
+            }
+            ArgType::Error => {
+                return Err(RizeError {
+                    type_: RizeErrorType::Assemble,
+                    message: "Cannot encode an invalid argument.".to_string(),
+                })
+            }
+        };
+        modes |= mode << (MODE_BITS * (2 - i as u16));
+        words[i + 1] = field;
+    }
+
+    words[0] = (opcode.to_byte() as u16) << 8 | modes;
+    Ok(words)
+}
+
+/// Reverses [encode_instruction].
+pub fn decode_instruction(
+    words: &[u16],
+) -> Result<(OpCode, [ArgType; 3]), RizeError> {
+    if words.len() != INSTRUCTION_WORDS {
+        return Err(RizeError {
+            type_: RizeErrorType::Decode,
+            message: format!(
+                "Expected {} instruction words, got {}",
+                INSTRUCTION_WORDS,
+                words.len()
+            ),
+        });
+    }
+
+    let opcode_byte = (words[0] >> 8) as u8;
+    let opcode = OpCode::from_byte(opcode_byte).ok_or_else(|| RizeError {
+        type_: RizeErrorType::Decode,
+        message: format!("Unknown OpCode byte 0x{:02X}", opcode_byte),
+    })?;
+
+    let indirect = ((words[0] >> INDIRECT_SHIFT) & MODE_MASK) as usize;
+
+    let mut args: [ArgType; 3] = Default::default();
+    for (i, arg) in args.iter_mut().enumerate() {
+        let mode = (words[0] >> (MODE_BITS * (2 - i as u16))) & MODE_MASK;
+        let field = words[i + 1];
+        *arg = match mode {
+            MODE_REGISTER if indirect == i + 1 => {
+                let index = field & 0xFF;
+                let name = match index < GPR_INDEX_OFFSET as u16 {
+                    true => decode_register(index)?,
+                    false => decode_register(('a' as u16) << 8 | index)?,
+                };
+                ArgType::Indirect(name, (field >> 8) as u8 as i8)
+            }
+            MODE_REGISTER => ArgType::Register(decode_register(field)?),
+            MODE_IMMEDIATE => ArgType::Immediate(field),
+            MODE_MEM_ADDR => ArgType::MemAddr(field),
+            _ => ArgType::None,
+        };
+    }
+
+    Ok((opcode, args))
+}
+
+/// Formats a decoded Operand the way it would be written in azzembly.
+pub fn format_arg(arg: &ArgType) -> String {
+    match arg {
+        ArgType::None => String::new(),
+        ArgType::Error => "?".to_string(),
+        ArgType::Register(name) => name.to_uppercase(),
+        ArgType::MemAddr(addr) => format!("0x{:04X}", addr),
+        ArgType::Immediate(imm) => imm.to_string(),
+        ArgType::Symbol(sym) => format!(".{}", sym),
+        ArgType::Indirect(name, 0) => format!("[{}]", name.to_uppercase()),
+        ArgType::Indirect(name, offset) => {
+            format!("[{}{:+}]", name.to_uppercase(), offset)
+        }
+    }
+}
+
+/// Pointers hold full 16-bit Addresses, so only whole Registers qualify.
+/// Returns the Register Index, the Section is implied.
+fn encode_pointer_register(name: &str) -> Result<u16, RizeError> {
+    let field = encode_register(name)?;
+    let section = (field >> 8) as u8 as char;
+    let index = field & 0xFF;
+
+    let is_gpr = index >= GPR_INDEX_OFFSET as u16;
+    if (is_gpr && section != 'a') || (!is_gpr && section != '\0') {
+        return Err(RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!(
+                "'{}' can't hold an Address, Pointers have to be whole Registers.",
+                name
+            ),
+        });
+    }
+    Ok(index)
+}
+
+/// Whether `name` is a Register, in any Section, or 'fr' itself.
+pub fn is_register_name(name: &str) -> bool {
+    name.eq_ignore_ascii_case(FLAG_REGISTER) || encode_register(name).is_ok()
+}
+
+fn encode_register(name: &str) -> Result<u16, RizeError> {
+    let name = name.to_ascii_lowercase();
+
+    if let Some(bit) = name.strip_prefix(FLAG_REGISTER) {
+        let index = FIXED_REGISTERS
+            .iter()
+            .position(|r| *r == FLAG_REGISTER)
+            .expect("'fr' is a fixed Register");
+        return match bit.chars().collect::<Vec<char>>()[..] {
+            [bit @ 'a'..='h'] => Ok((bit as u16) << 8 | index as u16),
+            _ => Err(RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!(
+                    "'{}' has to be accessed bitwise, as '{}a'..'{}h'.",
+                    name, FLAG_REGISTER, FLAG_REGISTER
+                ),
+            }),
+        };
+    }
+    if let Some(index) = FIXED_REGISTERS.iter().position(|r| *r == name) {
+        return Ok(index as u16);
+    }
+
+    let chars: Vec<char> = name.chars().collect();
+    let unknown_register = || RizeError {
+        type_: RizeErrorType::Assemble,
+        message: format!("Unknown register '{}'.", name),
+    };
+
+    if chars.first() != Some(&'g') || !(2..=3).contains(&chars.len()) {
+        return Err(unknown_register());
+    }
+    if !chars[1].is_ascii_lowercase() {
+        return Err(unknown_register());
+    }
+    let section = chars.get(2).copied().unwrap_or('a');
+    if !GPR_SECTIONS.contains(&section) {
+        return Err(unknown_register());
+    }
+
+    let index = GPR_INDEX_OFFSET + (chars[1] as u8 - b'a');
+    Ok((section as u16) << 8 | index as u16)
+}
+
+fn decode_register(field: u16) -> Result<String, RizeError> {
+    let index = (field & 0xFF) as u8;
+    let section = (field >> 8) as u8 as char;
+
+    if index < GPR_INDEX_OFFSET && section != '\0' {
+        let is_flag_register =
+            FIXED_REGISTERS.get(index as usize) == Some(&FLAG_REGISTER);
+        if !is_flag_register || !('a'..='h').contains(&section) {
+            return Err(RizeError {
+                type_: RizeErrorType::Decode,
+                message: format!("Invalid register encoding 0x{:04X}", field),
+            });
+        }
+        return Ok(format!("{}{}", FLAG_REGISTER, section));
+    }
+    if index < GPR_INDEX_OFFSET {
+        return FIXED_REGISTERS
+            .get(index as usize)
+            .map(|name| name.to_string())
+            .ok_or_else(|| RizeError {
+                type_: RizeErrorType::Decode,
+                message: format!("Unknown register index 0x{:02X}", index),
+            });
+    }
+
+    let gpr_index = index - GPR_INDEX_OFFSET;
+    if gpr_index >= 26 || !GPR_SECTIONS.contains(&section) {
+        return Err(RizeError {
+            type_: RizeErrorType::Decode,
+            message: format!("Invalid register encoding 0x{:04X}", field),
+        });
+    }
+
+    let letter = (b'a' + gpr_index) as char;
+    Ok(format!("g{}{}", letter, section))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(opcode: OpCode, args: [ArgType; 3]) {
+        let words = encode_instruction(opcode, [&args[0], &args[1], &args[2]])
+            .expect("encodes");
+        assert_eq!(decode_instruction(&words), Ok((opcode, args)));
+    }
+
+    fn register(name: &str) -> ArgType {
+        ArgType::Register(name.to_string())
+    }
+
+    #[test]
+    fn round_trips_every_operand_kind() {
+        round_trip(
+            OpCode::ADD,
+            [register("gaa"), ArgType::Immediate(0xFFFF), register("gcb")],
+        );
+        round_trip(
+            OpCode::MOV,
+            [ArgType::MemAddr(0xFF02), register("gzd"), ArgType::None],
+        );
+        round_trip(
+            OpCode::JMP,
+            [ArgType::MemAddr(0), ArgType::None, ArgType::None],
+        );
+        round_trip(OpCode::HALT, Default::default());
+    }
+
+    #[test]
+    fn round_trips_fr_bits() {
+        round_trip(
+            OpCode::MOV,
+            [register("fra"), register("frh"), ArgType::None],
+        );
+    }
+
+    #[test]
+    fn round_trips_upper_sections() {
+        round_trip(
+            OpCode::SUB,
+            [register("gae"), register("gbf"), register("gcg")],
+        );
+    }
+
+    #[test]
+    fn round_trips_indirect_operands() {
+        round_trip(
+            OpCode::MOV,
+            [
+                register("gba"),
+                ArgType::Indirect("gaa".to_string(), -128),
+                ArgType::None,
+            ],
+        );
+        round_trip(
+            OpCode::MOV,
+            [
+                ArgType::Indirect("sp".to_string(), 127),
+                ArgType::Immediate(7),
+                ArgType::None,
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_indirect_operands() {
+        let pointer = ArgType::Indirect("gaa".to_string(), 0);
+        assert!(encode_instruction(
+            OpCode::MOV,
+            [&pointer, &pointer, &ArgType::None]
+        )
+        .is_err());
+
+        let half = ArgType::Indirect("gab".to_string(), 0);
+        assert!(encode_instruction(
+            OpCode::MOV,
+            [&half, &ArgType::None, &ArgType::None]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_words() {
+        assert!(decode_instruction(&[0xFF00, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn formats_operands_like_azzembly() {
+        assert_eq!(format_arg(&register("gab")), "GAB");
+        assert_eq!(format_arg(&ArgType::Immediate(7)), "7");
+        assert_eq!(format_arg(&ArgType::MemAddr(0x2A)), "0x002A");
+        assert_eq!(
+            format_arg(&ArgType::Indirect("gaa".to_string(), -4)),
+            "[GAA-4]"
+        );
+    }
+}
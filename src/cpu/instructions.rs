@@ -0,0 +1,1366 @@
+use crate::*;
+
+/// Gets a mutable reference to a register by name.
+pub(crate) fn get_register_mut<'a>(
+    registers: &'a mut Registers,
+    reg_name: &str,
+) -> Result<&'a mut Register, RizeError> {
+    registers.get(reg_name).ok_or_else(|| RizeError {
+        type_: RizeErrorType::RegisterRead, // Or RegisterWrite?
+        message: format!("Register '{}' not found!", reg_name),
+    })
+}
+
+/// The Address an Indirect Operand points to, wrapping around the Address Space.
+pub(crate) fn indirect_address(
+    registers: &mut Registers,
+    reg_name: &str,
+    offset: i8,
+) -> Result<u16, RizeError> {
+    let base = get_register_mut(registers, reg_name)?.read_section_u16()?;
+    Ok(base.wrapping_add_signed(offset as i16))
+}
+
+/// Determines the value of an operand (Register, Immediate, or Memory Address).
+/// Reads section-aware for registers.
+pub(crate) fn get_operand_value(
+    registers: &mut Registers,
+    memory: &Bus,
+    arg: &ArgType,
+) -> Result<u16, RizeError> {
+    match arg {
+        ArgType::Register(reg_name) => {
+            let register = get_register_mut(registers, reg_name)?;
+            // Use the new trait method
+            register.read_section_u16()
+        }
+        ArgType::Immediate(imm) => Ok(*imm),
+        ArgType::MemAddr(addr) => {
+            memory.read(*addr) // read already returns Result<u16, RizeError>
+        }
+        ArgType::Indirect(reg_name, offset) => {
+            memory.read(indirect_address(registers, reg_name, *offset)?)
+        }
+        ArgType::Symbol(sym) => Err(RizeError {
+            type_: RizeErrorType::Decode, // Or Execute?
+            message: format!(
+                "Cannot use symbol '.{}' as an operand value.",
+                sym
+            ),
+        }),
+        ArgType::None | ArgType::Error => Err(RizeError {
+            type_: RizeErrorType::Decode, // Or Execute?
+            message:
+                "Invalid/None ArgType encountered where value operand expected."
+                    .to_string(),
+        }),
+    }
+}
+
+/// Determines the destination register for instructions with an optional 3rd argument.
+/// Returns a mutable reference to the destination register.
+/// UPDATED: Also returns the name of the destination register as a String.
+pub(crate) fn determine_destination_register_mut<'a>(
+    registers: &'a mut Registers,
+    arg1: &ArgType,
+    arg3_opt: &Option<ArgType>,
+) -> Result<(&'a mut Register, String), RizeError> {
+    match arg3_opt {
+        // If arg3 is provided and is a register
+        Some(ArgType::Register(reg3_name)) => {
+            let reg_ref = get_register_mut(registers, reg3_name)?;
+            Ok((reg_ref, reg3_name.clone()))
+        }
+        // If arg3 is None or a comment, use arg1 (which must be a register)
+        None | Some(ArgType::None) => {
+            if let ArgType::Register(reg1_name) = arg1 {
+                let reg_ref = get_register_mut(registers, reg1_name)?;
+                Ok((reg_ref, reg1_name.clone()))
+            } else {
+                Err(RizeError {
+                    type_: RizeErrorType::Execute,
+                    message: "Destination (arg1) must be a Register when arg3 is omitted."
+                        .to_string(),
+                })
+            }
+        }
+        // If arg3 is provided but is not a register
+        Some(_) => Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message:
+                "Third argument (destination) must be a Register or omitted."
+                    .to_string(),
+        }),
+    }
+}
+
+/// Writes a single 1-bit Flag Register.
+pub(crate) fn write_flag(
+    registers: &mut Registers,
+    flag: &str,
+    value: bool,
+) -> Result<(), RizeError> {
+    registers
+        .get(flag)
+        .ok_or_else(|| RizeError {
+            type_: RizeErrorType::RegisterRead,
+            message: format!("Flag register '{}' not found", flag),
+        })?
+        .write_bool(value)
+}
+
+/// How many Bits an Operand has, see [Register::section_width].
+/// Everything but a Register is a full Word.
+fn operand_width(
+    registers: &mut Registers,
+    arg: &ArgType,
+) -> Result<usize, RizeError> {
+    match arg {
+        ArgType::Register(name) => {
+            Ok(get_register_mut(registers, name)?.section_width())
+        }
+        _ => Ok(CPU_BITTAGE),
+    }
+}
+
+/// The lowest `width` Bits set.
+fn width_mask(width: usize) -> u32 {
+    (1u32 << width) - 1
+}
+
+/// The Sign Bit of a `width`-bit Number.
+fn width_msb(width: usize) -> u32 {
+    1u32 << (width - 1)
+}
+
+/// Reads the lowest `width` Bits of `value` as a Two's Complement Number.
+fn sign_extend(value: u16, width: usize) -> i32 {
+    let shift = 32 - width;
+    ((value as i32) << shift) >> shift
+}
+
+/// Reads an Operand as a signed Number, a Register in its own Section Width,
+/// anything else in `width`.
+fn signed_operand(
+    registers: &mut Registers,
+    bus: &Bus,
+    arg: &ArgType,
+    width: usize,
+) -> Result<i32, RizeError> {
+    let value = get_operand_value(registers, bus, arg)?;
+    let width = match arg {
+        ArgType::Register(_) => operand_width(registers, arg)?,
+        _ => width,
+    };
+    Ok(sign_extend(value, width))
+}
+
+/// Cuts a signed Result down to `width` Bits,
+/// together with whether it didn't fit.
+fn wrap_signed(value: i32, width: usize) -> (u16, bool) {
+    let result = (value as u32 & width_mask(width)) as u16;
+    (result, sign_extend(result, width) != value)
+}
+
+/// Computes ARG1 + ARG2 (+ 1 with `carry`) within `width` Bits
+/// and sets all Flags from it, shared by ADD and ADC.
+fn add_with_flags(
+    registers: &mut Registers,
+    v1: u16,
+    v2: u16,
+    carry: bool,
+    width: usize,
+) -> Result<u16, RizeError> {
+    let mask = width_mask(width);
+    let msb = width_msb(width);
+    let (v1, v2) = (v1 as u32 & mask, v2 as u32 & mask);
+    let sum = v1 + v2 + carry as u32;
+    let result = sum & mask;
+
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(registers, FLAG_NEGATIVE, result & msb != 0)?;
+    // Carry Flag (fc): Set if unsigned addition resulted in carry
+    write_flag(registers, FLAG_CARRY, sum > mask)?;
+    // Overflow Flag (fo): Set if signed addition resulted in overflow
+    let overflow = (v1 & msb == v2 & msb) && (result & msb != v1 & msb);
+    write_flag(registers, FLAG_OVERFLOW, overflow)?;
+
+    Ok(result as u16)
+}
+
+/// Computes ARG1 - ARG2 (- 1 with `borrow`) within `width` Bits
+/// and sets all Flags from it, shared by SUB, SBB, CMP and NEG.
+fn subtract_with_flags(
+    registers: &mut Registers,
+    v1: u16,
+    v2: u16,
+    borrow: bool,
+    width: usize,
+) -> Result<u16, RizeError> {
+    let mask = width_mask(width);
+    let msb = width_msb(width);
+    let (v1, v2) = (v1 as u32 & mask, v2 as u32 & mask);
+    // Perform subtraction using wrapping arithmetic
+    let result = v1.wrapping_sub(v2).wrapping_sub(borrow as u32) & mask;
+
+    // Zero Flag (fz): Set if result is 0
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    // Negative Flag (fn): Set if MSB of result is 1
+    write_flag(registers, FLAG_NEGATIVE, result & msb != 0)?;
+    // Carry Flag (fc): Set if unsigned subtraction resulted in borrow
+    write_flag(registers, FLAG_CARRY, v1 < v2 + borrow as u32)?;
+    // Overflow Flag (fo): Set if signed subtraction resulted in overflow
+    let overflow = (v1 & msb != v2 & msb) && (result & msb != v1 & msb);
+    write_flag(registers, FLAG_OVERFLOW, overflow)?;
+
+    Ok(result as u16)
+}
+
+/// Computes ARG1 & ARG2 and sets all Flags from it, shared by AND and TEST.
+/// A bitwise AND can neither carry nor overflow, so 'fc' and 'fo' are cleared.
+fn and_with_flags(
+    registers: &mut Registers,
+    v1: u16,
+    v2: u16,
+    width: usize,
+) -> Result<u16, RizeError> {
+    let result = v1 & v2 & width_mask(width) as u16;
+
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(
+        registers,
+        FLAG_NEGATIVE,
+        result as u32 & width_msb(width) != 0,
+    )?;
+    write_flag(registers, FLAG_CARRY, false)?;
+    write_flag(registers, FLAG_OVERFLOW, false)?;
+
+    Ok(result)
+}
+
+pub(crate) fn mov(
+    arg1: &ArgType, // Destination (Register or MemAddr)
+    arg2: &ArgType, // Source (Register, Immediate, MemAddr)
+    registers: &mut Registers,
+    memory: &mut Bus, // Needs mutable memory for MemAddr dest
+) -> Result<(), RizeError> {
+    let source_value = get_operand_value(registers, memory, arg2)?;
+
+    match arg1 {
+        ArgType::Register(dest_reg_name) => {
+            let register = get_register_mut(registers, dest_reg_name)?;
+            // Use the new trait method
+            register.write_section_u16(source_value)
+        }
+        ArgType::MemAddr(dest_addr) => {
+            memory.write(*dest_addr, source_value) // write returns Result
+        }
+        ArgType::Indirect(reg_name, offset) => {
+            let dest_addr = indirect_address(registers, reg_name, *offset)?;
+            memory.write(dest_addr, source_value)
+        }
+        _ => Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message:
+                "MOV destination (arg1) must be Register, MemAddr or Indirect."
+                    .to_string(),
+        }),
+    }
+}
+
+pub(crate) fn add(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    // Validate arg1 is a register and get its value
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+
+    // Ensure arg1 is a register (destination or source)
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "ADD requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+
+    // Determine destination register using helper
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    let width = operand_width(registers, arg1)?;
+    let result = add_with_flags(registers, v1, v2, false, width)?;
+
+    // Get register ref again (determine_... returns name now)
+    let dest_register = get_register_mut(registers, &dest_name)?;
+    // Use section-aware trait method
+    dest_register.write_section_u16(result)
+}
+
+pub(crate) fn sub(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    r_memory: &Bus,
+) -> Result<(), RizeError> {
+    // Validate arg1 is a register and get its value
+    let v1 = get_operand_value(registers, r_memory, arg1)?;
+    // Get the value of arg2 (can be Register or Immediate)
+    let v2 = get_operand_value(registers, r_memory, arg2)?;
+
+    // Ensure arg1 is a register (destination or source)
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "SUB requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+
+    // Determine destination register using helper
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    let width = operand_width(registers, arg1)?;
+    let result = subtract_with_flags(registers, v1, v2, false, width)?;
+
+    // Get register ref again
+    let dest_register = get_register_mut(registers, &dest_name)?;
+    // Use section-aware trait method
+    dest_register.write_section_u16(result)
+}
+
+/// ADD, plus 1 if 'fc' is set.
+/// Chaining ADD on the lower Words with ADC on the upper Words
+/// adds Numbers wider than one Register.
+pub(crate) fn adc(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "ADC requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+    let carry = read_flag(registers, FLAG_CARRY)?;
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    let width = operand_width(registers, arg1)?;
+    let result = add_with_flags(registers, v1, v2, carry, width)?;
+
+    get_register_mut(registers, &dest_name)?.write_section_u16(result)
+}
+
+/// SUB, minus 1 if 'fc' is set, see [adc].
+pub(crate) fn sbb(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "SBB requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+    let borrow = read_flag(registers, FLAG_CARRY)?;
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    let width = operand_width(registers, arg1)?;
+    let result = subtract_with_flags(registers, v1, v2, borrow, width)?;
+
+    get_register_mut(registers, &dest_name)?.write_section_u16(result)
+}
+
+/// Points 'mar' at `address` and reads the Bus there into 'mdr'.
+pub(crate) fn read_through_mdr(
+    registers: &mut Registers,
+    memory: &Bus,
+    address: u16,
+) -> Result<u16, RizeError> {
+    get_register_mut(registers, MEMORY_ADDRESS_REGISTER)?
+        .write_section_u16(address)?;
+    let data = memory.read(address)?;
+    get_register_mut(registers, MEMORY_DATA_REGISTER)?
+        .write_section_u16(data)?;
+    Ok(data)
+}
+
+/// Points 'mar' at `address`, puts `data` into 'mdr' and writes it to the Bus.
+pub(crate) fn write_through_mdr(
+    registers: &mut Registers,
+    memory: &mut Bus,
+    address: u16,
+    data: u16,
+) -> Result<(), RizeError> {
+    get_register_mut(registers, MEMORY_ADDRESS_REGISTER)?
+        .write_section_u16(address)?;
+    get_register_mut(registers, MEMORY_DATA_REGISTER)?
+        .write_section_u16(data)?;
+    memory.write(address, data)
+}
+
+/// Determines which Memory Address an operand refers to.
+/// Registers and Immediates are used as the Address itself.
+fn get_operand_address(
+    registers: &mut Registers,
+    arg: &ArgType,
+) -> Result<u16, RizeError> {
+    match arg {
+        ArgType::MemAddr(addr) => Ok(*addr),
+        ArgType::Indirect(reg_name, offset) => {
+            indirect_address(registers, reg_name, *offset)
+        }
+        ArgType::Immediate(imm) => Ok(*imm),
+        ArgType::Register(reg_name) => {
+            get_register_mut(registers, reg_name)?.read_section_u16()
+        }
+        _ => Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "Expected a Memory Address, Register or Immediate."
+                .to_string(),
+        }),
+    }
+}
+
+pub(crate) fn st(
+    arg1: &ArgType, // Target Address
+    arg2: &ArgType, // Source Value
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let address = get_operand_address(registers, arg1)?;
+    let data = get_operand_value(registers, memory, arg2)?;
+    write_through_mdr(registers, memory, address, data)
+}
+
+pub(crate) fn ld(
+    arg1: &ArgType, // Target Register
+    arg2: &ArgType, // Source Address
+    registers: &mut Registers,
+    memory: &Bus,
+) -> Result<(), RizeError> {
+    let ArgType::Register(dest_reg_name) = arg1 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "LD target (arg1) must be a Register.".to_string(),
+        });
+    };
+
+    let address = get_operand_address(registers, arg2)?;
+    let data = read_through_mdr(registers, memory, address)?;
+    get_register_mut(registers, dest_reg_name)?.write_section_u16(data)
+}
+
+/// Reads a SWP Operand, which has to be writable afterwards.
+fn read_swap_operand(
+    registers: &mut Registers,
+    memory: &Bus,
+    arg: &ArgType,
+) -> Result<u16, RizeError> {
+    match arg {
+        ArgType::Register(reg_name) => {
+            get_register_mut(registers, reg_name)?.read_section_u16()
+        }
+        ArgType::MemAddr(addr) => read_through_mdr(registers, memory, *addr),
+        ArgType::Indirect(reg_name, offset) => {
+            let addr = indirect_address(registers, reg_name, *offset)?;
+            read_through_mdr(registers, memory, addr)
+        }
+        _ => Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "SWP operands must be Registers or MemAddrs.".to_string(),
+        }),
+    }
+}
+
+fn write_swap_operand(
+    registers: &mut Registers,
+    memory: &mut Bus,
+    arg: &ArgType,
+    value: u16,
+) -> Result<(), RizeError> {
+    match arg {
+        ArgType::Register(reg_name) => {
+            get_register_mut(registers, reg_name)?.write_section_u16(value)
+        }
+        ArgType::MemAddr(addr) => {
+            write_through_mdr(registers, memory, *addr, value)
+        }
+        ArgType::Indirect(reg_name, offset) => {
+            let addr = indirect_address(registers, reg_name, *offset)?;
+            write_through_mdr(registers, memory, addr, value)
+        }
+        _ => Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "SWP operands must be Registers or MemAddrs.".to_string(),
+        }),
+    }
+}
+
+/// Swaps ARG1 and ARG2, each only within its own Register Section.
+/// The optional Temp Register (ARG3) ends up holding the old ARG1.
+pub(crate) fn swp(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let v1 = read_swap_operand(registers, memory, arg1)?;
+    let v2 = read_swap_operand(registers, memory, arg2)?;
+
+    if let Some(temp) = arg3_opt {
+        let ArgType::Register(temp_name) = temp else {
+            return Err(RizeError {
+                type_: RizeErrorType::Execute,
+                message: "SWP temp (arg3) must be a Register.".to_string(),
+            });
+        };
+        get_register_mut(registers, temp_name)?.write_section_u16(v1)?;
+    }
+
+    write_swap_operand(registers, memory, arg1, v2)?;
+    write_swap_operand(registers, memory, arg2, v1)
+}
+
+pub(crate) fn and(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+
+    if !matches!(arg1, ArgType::Register(_))
+        || !matches!(arg2, ArgType::Register(_))
+    {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "AND requires register operands (arg1, arg2).".to_string(),
+        });
+    }
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    let width = operand_width(registers, arg1)?;
+    let result = and_with_flags(registers, v1, v2, width)?;
+    // Get register ref again
+    let dest_register = get_register_mut(registers, &dest_name)?;
+    // Use section-aware trait method
+    dest_register.write_section_u16(result)
+}
+
+pub(crate) fn or(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+
+    if !matches!(arg1, ArgType::Register(_))
+        || !matches!(arg2, ArgType::Register(_))
+    {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "OR requires register operands (arg1, arg2).".to_string(),
+        });
+    }
+
+    let result = v1 | v2;
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+    // Get register ref again
+    let dest_register = get_register_mut(registers, &dest_name)?;
+    // Use section-aware trait method
+    dest_register.write_section_u16(result)
+}
+
+pub(crate) fn xor(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+
+    if !matches!(arg1, ArgType::Register(_))
+        || !matches!(arg2, ArgType::Register(_))
+    {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "XOR requires register operands (arg1, arg2).".to_string(),
+        });
+    }
+
+    let result = v1 ^ v2;
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+    // Get register ref again
+    let dest_register = get_register_mut(registers, &dest_name)?;
+    // Use section-aware trait method
+    dest_register.write_section_u16(result)
+}
+
+/// Sets the Flags like SUB, without writing the Result anywhere.
+pub(crate) fn cmp(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    registers: &mut Registers,
+    memory: &Bus,
+) -> Result<(), RizeError> {
+    let v1 = get_operand_value(registers, memory, arg1)?;
+    let v2 = get_operand_value(registers, memory, arg2)?;
+
+    let width = operand_width(registers, arg1)?;
+    subtract_with_flags(registers, v1, v2, false, width).map(|_| ())
+}
+
+/// Sets the Flags like AND, without writing the Result anywhere.
+pub(crate) fn test(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    registers: &mut Registers,
+    memory: &Bus,
+) -> Result<(), RizeError> {
+    let v1 = get_operand_value(registers, memory, arg1)?;
+    let v2 = get_operand_value(registers, memory, arg2)?;
+
+    let width = operand_width(registers, arg1)?;
+    and_with_flags(registers, v1, v2, width).map(|_| ())
+}
+
+pub(crate) fn not(
+    arg1: &ArgType,
+    registers: &mut Registers,
+) -> Result<(), RizeError> {
+    if let ArgType::Register(reg_name) = arg1 {
+        let register = get_register_mut(registers, reg_name)?;
+        let v1 = register.read_section_u16()?;
+        let result = !v1;
+        register.write_section_u16(result)
+    } else {
+        Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "NOT requires a Register operand (arg1).".to_string(),
+        })
+    }
+}
+
+/// Two's Complement Negation, sets Flags like 'SUB' from 0.
+pub(crate) fn neg(
+    arg1: &ArgType,
+    registers: &mut Registers,
+) -> Result<(), RizeError> {
+    let ArgType::Register(reg_name) = arg1 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "NEG requires a Register operand (arg1).".to_string(),
+        });
+    };
+    let register = get_register_mut(registers, reg_name)?;
+    let width = register.section_width();
+    let value = register.read_section_u16()?;
+    let result = subtract_with_flags(registers, 0, value, false, width)?;
+    get_register_mut(registers, reg_name)?.write_section_u16(result)
+}
+
+/// Shifts or rotates ARG1 by ARG2 (default 1) Bits, one Bit at a Time,
+/// within the Width of ARG1's Section.
+///
+/// ### Dev Metadata
+/// | OPCODE | Bit shifted in         | Bit shifted out |
+/// | ------ | ---------------------- | --------------- |
+/// | SHL    | 0                      | MSB -> 'fc'     |
+/// | SHR    | 0                      | LSB -> 'fc'     |
+/// | SAR    | the old MSB (the Sign) | LSB -> 'fc'     |
+/// | ROL    | the old MSB            | MSB -> 'fc'     |
+/// | ROR    | the old LSB            | LSB -> 'fc'     |
+/// | RCL    | 'fc'                   | MSB -> 'fc'     |
+/// | RCR    | 'fc'                   | LSB -> 'fc'     |
+///
+/// - Shifts also set 'fz' and 'fn' and clear 'fo', Rotations only touch 'fc'
+/// - shifting by 0 leaves every Flag alone
+pub(crate) fn shift(
+    op: OpCode,
+    arg1: &ArgType, // Target Register
+    arg2: &ArgType, // Amount Immediate (Optional, defaults to 1)
+    registers: &mut Registers,
+) -> Result<(), RizeError> {
+    let ArgType::Register(target_reg_name) = arg1 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: format!("{:?} target (arg1) must be a Register.", op),
+        });
+    };
+    let amount = match arg2 {
+        ArgType::Immediate(imm) => *imm as usize,
+        ArgType::None => 1, // Default shift amount
+        _ => {
+            return Err(RizeError {
+                type_: RizeErrorType::Execute,
+                message: format!(
+                    "{:?} amount (arg2) must be Immediate or omitted.",
+                    op
+                ),
+            })
+        }
+    };
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let target_register = get_register_mut(registers, target_reg_name)?;
+    let width = target_register.section_width();
+    let mut value = target_register.read_section_u16()? as u32;
+    let mut carry = read_flag(registers, FLAG_CARRY)?;
+
+    let msb = 1u32 << (width - 1);
+    let mask = (msb << 1) - 1;
+    // after this many Steps, Shifts don't change anymore and Rotations repeat
+    let period = match op {
+        OpCode::ROL | OpCode::ROR => width,
+        _ => width + 1,
+    };
+    let steps = match op {
+        OpCode::SHL | OpCode::SHR | OpCode::SAR => amount.min(period),
+        _ => (amount - 1) % period + 1,
+    };
+
+    for _ in 0..steps {
+        let (out, shifted_in) = match op {
+            OpCode::SHL => (value & msb != 0, false),
+            OpCode::SHR => (value & 1 != 0, false),
+            OpCode::SAR => (value & 1 != 0, value & msb != 0),
+            OpCode::ROL => (value & msb != 0, value & msb != 0),
+            OpCode::ROR => (value & 1 != 0, value & 1 != 0),
+            OpCode::RCL => (value & msb != 0, carry),
+            OpCode::RCR => (value & 1 != 0, carry),
+            _ => {
+                return Err(RizeError {
+                    type_: RizeErrorType::Execute,
+                    message: format!("{:?} is not a Shift.", op),
+                })
+            }
+        };
+        value = match op {
+            OpCode::SHL | OpCode::ROL | OpCode::RCL => {
+                ((value << 1) & mask) | shifted_in as u32
+            }
+            _ => (value >> 1) | if shifted_in { msb } else { 0 },
+        };
+        carry = out;
+    }
+    let result = value as u16;
+
+    write_flag(registers, FLAG_CARRY, carry)?;
+    if matches!(op, OpCode::SHL | OpCode::SHR | OpCode::SAR) {
+        write_flag(registers, FLAG_ZERO, result == 0)?;
+        write_flag(registers, FLAG_NEGATIVE, result as u32 & msb != 0)?;
+        write_flag(registers, FLAG_OVERFLOW, false)?;
+    }
+
+    get_register_mut(registers, target_reg_name)?.write_section_u16(result)
+}
+
+pub(crate) fn wdm(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3: &ArgType,
+    display_memory: &mut DisplayMemory,
+    registers: &mut Registers,
+    memory: &Bus, // Added memory
+) -> Result<(), RizeError> {
+    let val1: u16 = get_operand_value(registers, memory, arg1)?;
+    let val2: u16 = get_operand_value(registers, memory, arg2)?;
+    let val3: u16 = get_operand_value(registers, memory, arg3)?;
+
+    // info!("Val3: {:#016b}", val3);
+
+    let red: u8 = (val1 >> 8) as u8;
+    let green: u8 = (val1 & 0xFF) as u8;
+
+    let blue: u8 = (val2 >> 8) as u8;
+    let alpha: u8 = (val2 & 0xFF) as u8;
+
+    let x: u8 = (val3 >> 8) as u8;
+    let y: u8 = (val3 & 0xFF) as u8;
+
+    // info(format!("x: {x}, y: {y}"));
+
+    let color = [red, green, blue, alpha];
+
+    display_memory.set_pixel(x, y, color)
+}
+
+pub(crate) fn mul(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    r_memory: &Bus,
+) -> Result<(), RizeError> {
+    // Validate arg1 is a register and get its value
+    let v1 = get_operand_value(registers, r_memory, arg1)?;
+    // Get the value of arg2 (can be Register or Immediate)
+    let v2 = get_operand_value(registers, r_memory, arg2)?;
+
+    // Ensure arg1 is a register (destination or source)
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "MUL requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+
+    // Multiply within the Width of ARG1's Section
+    let width = operand_width(registers, arg1)?;
+    let product = v1 as u32 * v2 as u32;
+    let result = (product & width_mask(width)) as u16;
+    let (_, overflow) = wrap_signed(
+        signed_operand(registers, r_memory, arg1, width)?
+            * signed_operand(registers, r_memory, arg2, width)?,
+        width,
+    );
+
+    // Determine destination register using helper
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    // --- Set Flags ---
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(
+        registers,
+        FLAG_NEGATIVE,
+        result as u32 & width_msb(width) != 0,
+    )?;
+    // Carry Flag (fc): Set if the product doesn't fit into the Section
+    write_flag(registers, FLAG_CARRY, product > width_mask(width))?;
+    // Overflow Flag (fo): Set if the signed product doesn't fit into the Section
+    write_flag(registers, FLAG_OVERFLOW, overflow)?;
+    // --- End Set Flags ---
+
+    // Get register ref again
+    let dest_register = get_register_mut(registers, &dest_name)?;
+    // Use section-aware trait method
+    dest_register.write_section_u16(result)
+}
+
+pub(crate) fn div(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    r_memory: &Bus,
+) -> Result<(), RizeError> {
+    // Validate arg1 is a register and get its value
+    let v1 = get_operand_value(registers, r_memory, arg1)?;
+    // Get the value of arg2 (can be Register or Immediate)
+    let v2 = get_operand_value(registers, r_memory, arg2)?;
+
+    // Ensure arg1 is a register (destination or source)
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "DIV requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+
+    // Check for division by zero
+    if v2 == 0 {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "Division by zero".to_string(),
+        });
+    }
+
+    // Perform division
+    let result = v1.wrapping_div(v2);
+    let remainder = v1 % v2;
+    let width = operand_width(registers, arg1)?;
+    // Overflow can only occur in signed division when dividing the Minimum by -1
+    let overflow = signed_operand(registers, r_memory, arg1, width)?
+        == -(width_msb(width) as i32)
+        && signed_operand(registers, r_memory, arg2, width)? == -1;
+
+    // --- Set Flags ---
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(
+        registers,
+        FLAG_NEGATIVE,
+        result as u32 & width_msb(width) != 0,
+    )?;
+    // Carry Flag (fc): Set if there is a remainder
+    write_flag(registers, FLAG_CARRY, remainder != 0)?;
+    // Overflow Flag (fo): Set if signed division resulted in overflow
+    write_flag(registers, FLAG_OVERFLOW, overflow)?;
+    // --- End Set Flags ---
+
+    write_quotient(OpCode::DIV, arg1, arg3_opt, registers, result, remainder)
+}
+
+/// The Quotient always goes into ARG1, ARG3 optionally receives the Remainder,
+/// shared by DIV and IDIV.
+fn write_quotient(
+    op: OpCode,
+    arg1: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    quotient: u16,
+    remainder: u16,
+) -> Result<(), RizeError> {
+    if let ArgType::Register(dest_name) = arg1 {
+        get_register_mut(registers, dest_name)?.write_section_u16(quotient)?;
+    }
+    match arg3_opt {
+        None | Some(ArgType::None) => Ok(()),
+        Some(ArgType::Register(remainder_name)) => {
+            get_register_mut(registers, remainder_name)?
+                .write_section_u16(remainder)
+        }
+        Some(_) => Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: format!(
+                "{:?} remainder (arg3) must be a Register or omitted.",
+                op
+            ),
+        }),
+    }
+}
+
+/// The Remainder of ARG1 / ARG2, the Counterpart to 'DIV'.
+/// Sets 'fz' and 'fn' from the Remainder, clears 'fc' and 'fo'.
+pub(crate) fn modulo(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "MOD requires the first argument (arg1) to be a register."
+                .to_string(),
+        });
+    }
+    let v1 = get_operand_value(registers, bus, arg1)?;
+    let v2 = get_operand_value(registers, bus, arg2)?;
+
+    if v2 == 0 {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "Division by zero".to_string(),
+        });
+    }
+    let result = v1 % v2;
+    let msb = width_msb(operand_width(registers, arg1)?);
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(registers, FLAG_NEGATIVE, result as u32 & msb != 0)?;
+    write_flag(registers, FLAG_CARRY, false)?;
+    write_flag(registers, FLAG_OVERFLOW, false)?;
+
+    get_register_mut(registers, &dest_name)?.write_section_u16(result)
+}
+
+/// Like 'MUL', but reads both Operands as signed Numbers,
+/// see [signed_operand].
+/// 'fc' and 'fo' are set together,
+/// if the Product doesn't fit into ARG1's Section.
+pub(crate) fn imul(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message:
+                "IMUL requires the first argument (arg1) to be a register."
+                    .to_string(),
+        });
+    }
+    let width = operand_width(registers, arg1)?;
+    let v1 = signed_operand(registers, bus, arg1, width)?;
+    let v2 = signed_operand(registers, bus, arg2, width)?;
+
+    let (result, overflow) = wrap_signed(v1 * v2, width);
+
+    let (_dest_register, dest_name) =
+        determine_destination_register_mut(registers, arg1, arg3_opt)?;
+
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(
+        registers,
+        FLAG_NEGATIVE,
+        result as u32 & width_msb(width) != 0,
+    )?;
+    write_flag(registers, FLAG_CARRY, overflow)?;
+    write_flag(registers, FLAG_OVERFLOW, overflow)?;
+
+    get_register_mut(registers, &dest_name)?.write_section_u16(result)
+}
+
+/// Like 'DIV', but reads both Operands as signed Numbers,
+/// see [signed_operand].
+/// The Quotient is rounded towards Zero, the Remainder has ARG1's Sign.
+///
+/// ### Dev Metadata
+/// - 'fc' -> there is a Remainder
+/// - 'fo' -> the Minimum / -1 (-32768 / -1 for a full Register),
+///   the only Quotient that doesn't fit (wraps to the Minimum)
+pub(crate) fn idiv(
+    arg1: &ArgType,
+    arg2: &ArgType,
+    arg3_opt: &Option<ArgType>,
+    registers: &mut Registers,
+    bus: &Bus,
+) -> Result<(), RizeError> {
+    if !matches!(arg1, ArgType::Register(_)) {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message:
+                "IDIV requires the first argument (arg1) to be a register."
+                    .to_string(),
+        });
+    }
+    let width = operand_width(registers, arg1)?;
+    let v1 = signed_operand(registers, bus, arg1, width)?;
+    let v2 = signed_operand(registers, bus, arg2, width)?;
+
+    if v2 == 0 {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "Division by zero".to_string(),
+        });
+    }
+
+    let (result, overflow) = wrap_signed(v1 / v2, width);
+    let remainder = v1 % v2;
+
+    write_flag(registers, FLAG_ZERO, result == 0)?;
+    write_flag(
+        registers,
+        FLAG_NEGATIVE,
+        result as u32 & width_msb(width) != 0,
+    )?;
+    write_flag(registers, FLAG_CARRY, remainder != 0)?;
+    write_flag(registers, FLAG_OVERFLOW, overflow)?;
+
+    write_quotient(
+        OpCode::IDIV,
+        arg1,
+        arg3_opt,
+        registers,
+        result,
+        remainder as u16,
+    )
+}
+
+/// Reads a 1-bit Flag Register.
+fn read_flag(registers: &mut Registers, flag: &str) -> Result<bool, RizeError> {
+    let value = registers
+        .get(flag)
+        .ok_or_else(|| RizeError {
+            type_: RizeErrorType::RegisterRead,
+            message: format!("Flag register '{}' not found", flag),
+        })?
+        .read_section_u16()?;
+
+    Ok(value != 0)
+}
+
+/// Decides from the current Flags whether a Jump is taken.
+///
+/// ### Dev Metadata
+/// | OpCode    | Taken if                   |
+/// | --------- | -------------------------- |
+/// | JMP       | always                     |
+/// | JIZ / JNZ | fz / !fz                   |
+/// | JIC / JNC | fc / !fc                   |
+/// | JIO / JNO | fo / !fo                   |
+/// | JIN / JNN | fn / !fn                   |
+/// | JGT       | !fz && fn == fo (signed)   |
+/// | JLT       | fn != fo (signed)          |
+/// | JGE       | fn == fo (signed)          |
+/// | JLE       | fz \|\| fn != fo (signed)  |
+/// | JA        | !fc && !fz (unsigned)      |
+/// | JB        | fc (unsigned)              |
+fn branch_taken(
+    opcode: OpCode,
+    registers: &mut Registers,
+) -> Result<bool, RizeError> {
+    let zero = read_flag(registers, FLAG_ZERO)?;
+    let carry = read_flag(registers, FLAG_CARRY)?;
+    let overflow = read_flag(registers, FLAG_OVERFLOW)?;
+    let negative = read_flag(registers, FLAG_NEGATIVE)?;
+
+    let taken = match opcode {
+        OpCode::JMP => true,
+        OpCode::JIZ => zero,
+        OpCode::JNZ => !zero,
+        OpCode::JIC => carry,
+        OpCode::JNC => !carry,
+        OpCode::JIO => overflow,
+        OpCode::JNO => !overflow,
+        OpCode::JIN => negative,
+        OpCode::JNN => !negative,
+        OpCode::JGT => !zero && negative == overflow,
+        OpCode::JLT => negative != overflow,
+        OpCode::JGE => negative == overflow,
+        OpCode::JLE => zero || negative != overflow,
+        OpCode::JA => !carry && !zero,
+        OpCode::JB => carry,
+        _ => {
+            return Err(RizeError {
+                type_: RizeErrorType::Execute,
+                message: format!("{:?} is not a Jump.", opcode),
+            })
+        }
+    };
+
+    Ok(taken)
+}
+
+/// Shared by JMP and every conditional Jump.
+pub(crate) fn branch(
+    opcode: OpCode,
+    arg1: &ArgType, // Target Address
+    registers: &mut Registers,
+) -> Result<(), RizeError> {
+    if !branch_taken(opcode, registers)? {
+        return Ok(());
+    }
+
+    jmp(arg1, registers)
+}
+
+pub(crate) fn jmp(
+    arg1: &ArgType, // Target Address
+    registers: &mut Registers,
+) -> Result<(), RizeError> {
+    let ArgType::MemAddr(target) = arg1 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "Jump target (arg1) must be a Symbol or MemAddr."
+                .to_string(),
+        });
+    };
+
+    get_register_mut(registers, PROGRAM_COUNTER)?.write_section_u16(*target)
+}
+
+/// ### Dev Metadata
+/// - 'sp' points at the next free Slot,
+///   so an empty Stack has 'sp' at [Memory::stack_top]
+/// - the Stack lives between [Memory::stack_bottom] and [Memory::stack_top]
+pub(crate) fn push_value(
+    registers: &mut Registers,
+    bus: &mut Bus,
+    value: u16,
+) -> Result<(), RizeError> {
+    let (stack_bottom, stack_top) =
+        (bus.memory.stack_bottom(), bus.memory.stack_top());
+    let sp = get_register_mut(registers, STACK_POINTER)?.read_section_u16()?;
+    if !(stack_bottom..=stack_top).contains(&sp) {
+        return Err(RizeError {
+            type_: RizeErrorType::StackOverflow,
+            message: format!(
+                "Stack Overflow! sp: {}, Stack Range: {}-{}",
+                sp, stack_bottom, stack_top
+            ),
+        });
+    }
+
+    write_through_mdr(registers, bus, sp, value)?;
+    get_register_mut(registers, STACK_POINTER)?
+        .write_section_u16(sp.wrapping_sub(1))
+}
+
+fn pop_value(registers: &mut Registers, bus: &Bus) -> Result<u16, RizeError> {
+    let (stack_bottom, stack_top) =
+        (bus.memory.stack_bottom(), bus.memory.stack_top());
+    let sp = get_register_mut(registers, STACK_POINTER)?.read_section_u16()?;
+    if !(stack_bottom.wrapping_sub(1)..stack_top).contains(&sp) {
+        return Err(RizeError {
+            type_: RizeErrorType::StackUnderflow,
+            message: format!(
+                "Stack Underflow! sp: {}, Stack Range: {}-{}",
+                sp, stack_bottom, stack_top
+            ),
+        });
+    }
+
+    let sp = sp + 1;
+    get_register_mut(registers, STACK_POINTER)?.write_section_u16(sp)?;
+    read_through_mdr(registers, bus, sp)
+}
+
+pub(crate) fn push(
+    arg1: &ArgType, // Source Value
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let value = get_operand_value(registers, memory, arg1)?;
+    push_value(registers, memory, value)
+}
+
+pub(crate) fn pop(
+    arg1: &ArgType, // Target Register
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let ArgType::Register(dest_reg_name) = arg1 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "POP target (arg1) must be a Register.".to_string(),
+        });
+    };
+
+    let value = pop_value(registers, memory)?;
+    get_register_mut(registers, dest_reg_name)?.write_section_u16(value)
+}
+
+/// Pushes the Return Address (the current 'pc') and jumps to ARG1.
+pub(crate) fn call(
+    arg1: &ArgType, // Target Address
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let return_address =
+        get_register_mut(registers, PROGRAM_COUNTER)?.read_section_u16()?;
+    push_value(registers, memory, return_address)?;
+    jmp(arg1, registers)
+}
+
+pub(crate) fn ret(
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let return_address = pop_value(registers, memory)?;
+    get_register_mut(registers, PROGRAM_COUNTER)?
+        .write_section_u16(return_address)
+}
+
+/// Packs the Status Flags into one Word, to survive an Interrupt Handler.
+///
+/// ### Dev Metadata
+/// - Bit 0: 'fz', Bit 1: 'fc', Bit 2: 'fo', Bit 3: 'fn'
+pub(crate) fn read_status_flags(
+    registers: &mut Registers,
+) -> Result<u16, RizeError> {
+    let mut status = 0;
+    for (bit, flag) in STATUS_FLAGS.iter().enumerate() {
+        status |= (read_flag(registers, flag)? as u16) << bit;
+    }
+    Ok(status)
+}
+
+fn write_status_flags(
+    registers: &mut Registers,
+    status: u16,
+) -> Result<(), RizeError> {
+    for (bit, flag) in STATUS_FLAGS.iter().enumerate() {
+        write_flag(registers, flag, (status >> bit) & 1 == 1)?;
+    }
+    Ok(())
+}
+
+const STATUS_FLAGS: [&str; 4] =
+    [FLAG_ZERO, FLAG_CARRY, FLAG_OVERFLOW, FLAG_NEGATIVE];
+
+/// Returns from an Interrupt Handler, re-enabling Interrupts.
+/// Reverses [crate::Cpu::service_interrupt].
+pub(crate) fn iret(
+    registers: &mut Registers,
+    memory: &mut Bus,
+) -> Result<(), RizeError> {
+    let status = pop_value(registers, memory)?;
+    write_status_flags(registers, status)?;
+    ret(registers, memory)?;
+    write_flag(registers, FLAG_INTERRUPT, true)
+}
+
+/// Points the Interrupt Vector ARG1 at the Handler ARG2.
+pub(crate) fn vec(
+    arg1: &ArgType, // Interrupt Number
+    arg2: &ArgType, // Handler Address
+    bus: &mut Bus,
+) -> Result<(), RizeError> {
+    let ArgType::Immediate(interrupt) = arg1 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "VEC interrupt (arg1) must be an Immediate.".to_string(),
+        });
+    };
+    let ArgType::MemAddr(handler) = arg2 else {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: "VEC handler (arg2) must be a Symbol or MemAddr."
+                .to_string(),
+        });
+    };
+    if *interrupt as usize >= INTERRUPT_VECTORS {
+        return Err(RizeError {
+            type_: RizeErrorType::Execute,
+            message: format!(
+                "Interrupt {} does not exist, there are only {}.",
+                interrupt, INTERRUPT_VECTORS
+            ),
+        });
+    }
+
+    let table = bus.memory.interrupt_vector_table();
+    bus.write(table + interrupt, *handler)
+}
+
+/// Raises a Timer Interrupt every ARG1 executed Instructions, 0 stops it.
+pub(crate) fn tmr(
+    arg1: &ArgType, // Period
+    timer: &mut IntervalTimer,
+    registers: &mut Registers,
+    memory: &Bus,
+) -> Result<(), RizeError> {
+    let period = get_operand_value(registers, memory, arg1)?;
+    timer.set_period(period);
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::*;
+
+use super::instructions::*;
+
+/// Keeps track of raised Interrupts, until the [Cpu] services them.
+///
+/// ### Dev Metadata
+/// - one pending Bit per Interrupt, the lowest Number wins
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterruptController {
+    pending: u8,
+}
+
+impl InterruptController {
+    pub fn raise(&mut self, interrupt: u8) {
+        if (interrupt as usize) < INTERRUPT_VECTORS {
+            self.pending |= 1 << interrupt;
+        }
+    }
+
+    pub fn pending(&self) -> Option<u8> {
+        (self.pending != 0).then(|| self.pending.trailing_zeros() as u8)
+    }
+
+    fn acknowledge(&mut self, interrupt: u8) {
+        self.pending &= !(1 << interrupt);
+    }
+}
+
+/// Raises [TIMER_INTERRUPT] every `period` executed Instructions.
+/// A Period of 0 turns the Timer off.
+///
+/// ### Dev Metadata
+/// - only Instructions that started with Interrupts enabled are counted,
+///   so the Timer pauses inside Handlers (and for their 'IRET'),
+///   otherwise a Period shorter than the Handler would never leave it
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntervalTimer {
+    period: u16,
+    elapsed: u16,
+}
+
+impl IntervalTimer {
+    pub fn set_period(&mut self, period: u16) {
+        self.period = period;
+        self.elapsed = 0;
+    }
+
+    /// Counts one executed Instruction, returns true when the Timer fires.
+    pub fn tick(&mut self) -> bool {
+        if self.period == 0 {
+            return false;
+        }
+
+        self.elapsed += 1;
+        if self.elapsed < self.period {
+            return false;
+        }
+        self.elapsed = 0;
+        true
+    }
+}
+
+impl Cpu {
+    /// Whether the Interrupt Flag 'fi' is set.
+    pub fn interrupts_enabled(&self) -> bool {
+        self.registers
+            .all()
+            .get(FLAG_INTERRUPT)
+            .and_then(|flag| flag.read_section_u16().ok())
+            .is_some_and(|value| value == 1)
+    }
+
+    /// Whether [Cpu::service_interrupt] would do something right now.
+    pub fn interrupt_pending(&self) -> bool {
+        self.interrupts_enabled() && self.interrupts.pending().is_some()
+    }
+
+    /// Jumps into the Handler of the most urgent pending Interrupt.
+    /// Returns false if Interrupts are disabled or none is pending.
+    ///
+    /// ### Dev Metadata
+    /// 1) push 'pc', then the Status Flags (see 'IRET')
+    /// 2) disable Interrupts, so Handlers aren't interrupted themselves
+    /// 3) jump to the Address in the Interrupt Vector Table
+    ///     - a Vector of 0 means there is no Handler, the Interrupt is dropped
+    pub fn service_interrupt(&mut self) -> Result<bool, RizeError> {
+        if !self.interrupt_pending() {
+            return Ok(false);
+        }
+        let Some(interrupt) = self.interrupts.pending() else {
+            return Ok(false);
+        };
+        self.interrupts.acknowledge(interrupt);
+
+        let registers = &mut self.registers;
+        let bus = &mut self.bus;
+
+        let handler =
+            bus.read(bus.memory.interrupt_vector_table() + interrupt as u16)?;
+        if handler == 0 {
+            return Ok(false);
+        }
+
+        let return_address =
+            get_register_mut(registers, PROGRAM_COUNTER)?.read_section_u16()?;
+        push_value(registers, bus, return_address)?;
+        let status = read_status_flags(registers)?;
+        push_value(registers, bus, status)?;
+
+        write_flag(registers, FLAG_INTERRUPT, false)?;
+        get_register_mut(registers, PROGRAM_COUNTER)?
+            .write_section_u16(handler)?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::*;
+
+pub const KEY_BACKSPACE: u16 = 8;
+pub const KEY_ENTER: u16 = 10;
+pub const KEY_ESCAPE: u16 = 27;
+pub const KEY_UP: u16 = 0x80;
+pub const KEY_DOWN: u16 = 0x81;
+pub const KEY_LEFT: u16 = 0x82;
+pub const KEY_RIGHT: u16 = 0x83;
+
+/// Buffers Key Presses from a Frontend until a Program reads them.
+///
+/// ### Dev Metadata
+/// | Offset | Read                          | Write               |
+/// | ------ | ----------------------------- | ------------------- |
+/// | 0      | Amount of buffered Keys       | Clears the Buffer   |
+/// | 1      | Next Key (0 if there is none) | -                   |
+///
+/// - printable Keys are their ASCII Code, the rest uses the `KEY_` Constants
+/// - Clones share the same Buffer
+#[derive(Default, Debug, Clone)]
+pub struct Keyboard {
+    buffer: Arc<Mutex<VecDeque<u16>>>,
+}
+
+impl Keyboard {
+    /// Keys pressed while the Buffer is full are dropped.
+    pub const BUFFER_SIZE: usize = 16;
+
+    pub fn press(&self, key: u16) {
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        if buffer.len() < Self::BUFFER_SIZE {
+            buffer.push_back(key);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut buffer) = self.buffer.lock() {
+            buffer.clear();
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, VecDeque<u16>>, RizeError> {
+        self.buffer.lock().map_err(|_| RizeError {
+            type_: RizeErrorType::Device,
+            message: "Failed to acquire lock for the Keyboard Buffer"
+                .to_string(),
+        })
+    }
+}
+
+impl Device for Keyboard {
+    fn name(&self) -> &str {
+        "keyboard"
+    }
+
+    fn size(&self) -> u16 {
+        2
+    }
+
+    fn read(&self, offset: u16) -> Result<u16, RizeError> {
+        let mut buffer = self.lock()?;
+        match offset {
+            0 => Ok(buffer.len() as u16),
+            _ => Ok(buffer.pop_front().unwrap_or(0)),
+        }
+    }
+
+    fn write(&self, offset: u16, _value: u16) -> Result<(), RizeError> {
+        if offset != 0 {
+            return Err(RizeError {
+                type_: RizeErrorType::Device,
+                message: "The Keyboard's Data Port is read-only.".to_string(),
+            });
+        }
+        self.lock()?.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hands_out_keys_in_order() {
+        let keyboard = Keyboard::default();
+        keyboard.press('w' as u16);
+        keyboard.press(KEY_ENTER);
+
+        assert_eq!(keyboard.read(0), Ok(2));
+        assert_eq!(keyboard.read(1), Ok('w' as u16));
+        assert_eq!(keyboard.read(1), Ok(KEY_ENTER));
+        assert_eq!(keyboard.read(1), Ok(0));
+        assert_eq!(keyboard.read(0), Ok(0));
+    }
+
+    #[test]
+    fn drops_keys_once_the_buffer_is_full() {
+        let keyboard = Keyboard::default();
+        for key in 0..Keyboard::BUFFER_SIZE as u16 + 4 {
+            keyboard.press(key + 1);
+        }
+        assert_eq!(keyboard.read(0), Ok(Keyboard::BUFFER_SIZE as u16));
+        assert_eq!(keyboard.read(1), Ok(1));
+    }
+
+    #[test]
+    fn status_port_clears_and_data_port_is_read_only() {
+        let keyboard = Keyboard::default();
+        let handle = keyboard.clone();
+        handle.press('a' as u16);
+        assert_eq!(keyboard.read(0), Ok(1));
+
+        assert!(keyboard.write(1, 0).is_err());
+        keyboard.write(0, 0).unwrap();
+        assert_eq!(handle.read(0), Ok(0));
+    }
+
+    #[test]
+    fn programs_read_keys_from_the_bus() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("LD GAA 0xFF00\nLD GBA 0xFF01\nHALT")
+            .expect("assembles");
+        cpu.keyboard.press('d' as u16);
+        cpu.run_batch(10);
+
+        let mut read = |name: &str| {
+            cpu.registers.get(name).unwrap().read_section_u16().unwrap()
+        };
+        assert_eq!(read("gaa"), 1);
+        assert_eq!(read("gba"), 'd' as u16);
+    }
+}
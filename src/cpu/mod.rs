@@ -0,0 +1,1156 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::*;
+
+mod display;
+pub use display::*;
+
+mod encoding;
+pub use encoding::*;
+
+mod bus;
+pub use bus::*;
+
+mod instructions;
+use instructions::*;
+
+mod interrupts;
+pub use interrupts::*;
+
+mod keyboard;
+pub use keyboard::*;
+
+mod console;
+pub use console::*;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ProgramArg {
+    pub raw: String,
+    pub parsed: ArgType,
+}
+
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub enum ArgType {
+    #[default]
+    None,
+    Error,
+    Register(String),
+    MemAddr(u16),
+    Immediate(u16),
+    Symbol(String),
+    /// The Memory Address in a Register, plus an Offset: `[gaa+4]`
+    Indirect(String, i8),
+}
+
+/// The azzembly Source currently loaded into the [Cpu],
+/// together with the Instruction that is being worked on.
+///
+/// ### Dev Metadata
+/// - the encoded Instructions themselves live in [Memory]
+/// - `raw_opcode` and the `raw` Args are disassembled from 'ir',
+///   not copied from the Source
+#[derive(Default, Debug)]
+pub struct Program {
+    pub contents: String,
+    /// where the current Instruction was written
+    pub location: SourceTrace,
+    /// First Address after the last Instruction
+    pub end: u16,
+    pub symbols: HashMap<String, u16>,
+    pub source_lines: HashMap<u16, SourceTrace>,
+    pub metadata: Metadata,
+    pub raw_opcode: String,
+    pub opcode: OpCode,
+    pub arg1: ProgramArg,
+    pub arg2: ProgramArg,
+    pub arg3: ProgramArg,
+}
+
+impl Program {
+    fn clear_instruction(&mut self) {
+        self.raw_opcode = String::new();
+        self.opcode = OpCode::None;
+        self.arg1 = ProgramArg::default();
+        self.arg2 = ProgramArg::default();
+        self.arg3 = ProgramArg::default();
+    }
+}
+
+/// What happened during a single Cycle Stage or Step of the [Cpu].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepOutcome {
+    Continue,
+    Halted,
+    EndOfProgram,
+}
+
+/// A Rize-1 CPU that can be driven without any Frontend.
+///
+/// ### Dev Metadata
+/// - [Cpu::fetch], [Cpu::decode] and [Cpu::execute] mirror the Cycle Stages,
+///   so Frontends can visualise them one at a time
+/// - [Cpu::step] runs a full Cycle, [Cpu::run] runs until the Program stops
+pub struct Cpu {
+    pub registers: Registers,
+    pub bus: Bus,
+    pub display: DisplayMemory,
+    pub program: Program,
+    pub interrupts: InterruptController,
+    pub timer: IntervalTimer,
+    /// Handle to the Keyboard attached at [KEYBOARD_PORT]
+    pub keyboard: Keyboard,
+    /// Handle to the Console attached at [CONSOLE_PORT]
+    pub console: Console,
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        let mut cpu = Self {
+            registers: Registers::new(),
+            bus: Bus::new(),
+            display: DisplayMemory::init(),
+            program: Program::default(),
+            interrupts: InterruptController::default(),
+            timer: IntervalTimer::default(),
+            keyboard: Keyboard::default(),
+            console: Console::default(),
+        };
+        cpu.setup_registers();
+        cpu.bus
+            .attach(KEYBOARD_PORT, cpu.keyboard.clone())
+            .expect("The Keyboard is the first Device on the Bus");
+        cpu.bus
+            .attach(CONSOLE_PORT, cpu.console.clone())
+            .expect("The Console doesn't overlap the Keyboard");
+        cpu
+    }
+
+    /// (Re-)Creates all Registers, which also resets their Values.
+    /// The amount of GPRs comes from the loaded Program's [Metadata].
+    pub fn setup_registers(&mut self) {
+        self.registers = Registers::new();
+        let stack_top = self.bus.memory.stack_top();
+        let registers = &mut self.registers;
+
+        registers.insert("ir".into(), Register::init(INSTRUCTION_WIDTH));
+        registers.insert(PROGRAM_COUNTER.into(), Register::init(CPU_BITTAGE));
+        registers.insert("mar".into(), Register::init(CPU_BITTAGE));
+        registers.insert("mdr".into(), Register::init(CPU_BITTAGE));
+
+        let stack_pointer = Register::init(CPU_BITTAGE);
+        stack_pointer
+            .store_immediate(stack_top as usize)
+            .expect("A fresh Register is never locked");
+        registers.insert(STACK_POINTER.into(), stack_pointer);
+
+        registers.insert(FLAG_ZERO.into(), Register::init(1));
+        registers.insert(FLAG_CARRY.into(), Register::init(1));
+        registers.insert(FLAG_OVERFLOW.into(), Register::init(1));
+        registers.insert(FLAG_NEGATIVE.into(), Register::init(1));
+        registers.insert(FLAG_INTERRUPT.into(), Register::init(1));
+        registers
+            .insert(FLAG_REGISTER.into(), Register::init(FLAG_REGISTER_BITS));
+
+        for i in 0..self.program.metadata.gprs {
+            // Convert index to letter (0->a, 1->b, etc)
+            let letter = (b'a' + i as u8) as char;
+            let reg_name = format!("g{}", letter);
+            registers.insert(reg_name, Register::init(CPU_BITTAGE));
+        }
+    }
+
+    /// Assembles a new Program into a fresh [Memory], recreates the Registers
+    /// its Header asks for, and points the Program Counter at its first Instruction.
+    pub fn load_program(
+        &mut self,
+        contents: impl Into<String>,
+    ) -> Result<(), RizeError> {
+        let contents = contents.into();
+        let assembly = assemble(&contents)?;
+        self.load_assembly(contents, assembly)
+    }
+
+    /// Like [Cpu::load_program], but reads the Program from `path`,
+    /// see [assemble_file].
+    pub fn load_program_file(&mut self, path: &Path) -> Result<(), RizeError> {
+        let contents = fs::read_to_string(path).map_err(|e| RizeError {
+            type_: RizeErrorType::Assemble,
+            message: format!("Can't read {:?}: {}.", path, e),
+        })?;
+        let assembly = assemble_file(&contents, path)?;
+        self.load_assembly(contents, assembly)
+    }
+
+    fn load_assembly(
+        &mut self,
+        contents: String,
+        assembly: Assembly,
+    ) -> Result<(), RizeError> {
+        let mut memory = Memory::with_size(assembly.metadata.memory);
+        let vector_table = memory.interrupt_vector_table();
+        if PROGRAM_START as usize + assembly.words.len() > vector_table as usize
+        {
+            return Err(RizeError {
+                type_: RizeErrorType::Assemble,
+                message: format!(
+                    "Program needs {} words, but only {} fit below the Interrupt Vector Table of a {} word Memory.",
+                    assembly.words.len(),
+                    vector_table - PROGRAM_START,
+                    memory.size()
+                ),
+            });
+        }
+
+        for (offset, word) in assembly.words.iter().enumerate() {
+            memory.write(PROGRAM_START + offset as u16, *word)?;
+        }
+        self.bus.memory = memory;
+
+        self.program = Program {
+            contents,
+            end: assembly.end,
+            symbols: assembly.symbols,
+            source_lines: assembly.source_lines,
+            metadata: assembly.metadata,
+            ..Default::default()
+        };
+
+        self.interrupts = InterruptController::default();
+        self.timer = IntervalTimer::default();
+        self.keyboard.clear();
+        self.console.clear()?;
+        self.setup_registers();
+        get_register_mut(&mut self.registers, PROGRAM_COUNTER)?
+            .write_section_u16(PROGRAM_START)
+    }
+
+    /// ### Dev Metadata
+    /// - the Program Counter is read on every Fetch,
+    ///   in case the user overwrote the 'pc' Register manually
+    /// - every Instruction Word travels 'pc' -> 'mar' -> Memory -> 'mdr',
+    ///   and the full Instruction ends up in 'ir'
+    pub fn fetch(&mut self) -> Result<StepOutcome, RizeError> {
+        let registers = &mut self.registers;
+        let pc =
+            get_register_mut(registers, PROGRAM_COUNTER)?.read_section_u16()?;
+
+        if pc >= self.program.end {
+            self.program.clear_instruction();
+            return Ok(StepOutcome::EndOfProgram);
+        }
+
+        let mut words = [0u16; INSTRUCTION_WORDS];
+        for (offset, word) in words.iter_mut().enumerate() {
+            let address = pc.wrapping_add(offset as u16);
+            *word = read_through_mdr(registers, &self.bus, address)?;
+        }
+
+        get_register_mut(registers, INSTRUCTION_REGISTER)?
+            .store_words(&words)?;
+        get_register_mut(registers, PROGRAM_COUNTER)?
+            .write_section_u16(pc.wrapping_add(INSTRUCTION_WORDS as u16))?;
+
+        self.program.location = self
+            .program
+            .source_lines
+            .get(&pc)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(StepOutcome::Continue)
+    }
+
+    /// Splits the Instruction in 'ir' into its OpCode and Arguments.
+    pub fn decode(&mut self) -> Result<(), RizeError> {
+        let words =
+            get_register_mut(&mut self.registers, INSTRUCTION_REGISTER)?
+                .read_words()?;
+        let (opcode, [arg1, arg2, arg3]) = decode_instruction(&words)?;
+
+        let program = &mut self.program;
+        program.opcode = opcode;
+        program.raw_opcode = format!("{:?}", opcode);
+        program.arg1 = ProgramArg {
+            raw: format_arg(&arg1),
+            parsed: arg1,
+        };
+        program.arg2 = ProgramArg {
+            raw: format_arg(&arg2),
+            parsed: arg2,
+        };
+        program.arg3 = ProgramArg {
+            raw: format_arg(&arg3),
+            parsed: arg3,
+        };
+
+        Ok(())
+    }
+
+    pub fn execute(&mut self) -> Result<StepOutcome, RizeError> {
+        let counts_for_timer = self.interrupts_enabled();
+        let program = &mut self.program;
+        let registers = &mut self.registers;
+        let bus = &mut self.bus;
+
+        let arg3_option = match &program.arg3.parsed {
+            ArgType::None => None,
+            arg3 => Some(arg3.clone()),
+        };
+
+        match program.opcode {
+            OpCode::MOV => {
+                mov(&program.arg1.parsed, &program.arg2.parsed, registers, bus)
+            }
+            OpCode::SWP => swp(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::ADD => add(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::SUB => sub(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::ADC => adc(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::SBB => sbb(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::MUL => mul(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::DIV => div(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::MOD => modulo(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::IMUL => imul(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::IDIV => idiv(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::ST => {
+                st(&program.arg1.parsed, &program.arg2.parsed, registers, bus)
+            }
+            OpCode::LD => {
+                ld(&program.arg1.parsed, &program.arg2.parsed, registers, bus)
+            }
+            OpCode::AND => and(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::OR => or(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::XOR => xor(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &arg3_option,
+                registers,
+                bus,
+            ),
+            OpCode::CMP => {
+                cmp(&program.arg1.parsed, &program.arg2.parsed, registers, bus)
+            }
+            OpCode::TEST => {
+                test(&program.arg1.parsed, &program.arg2.parsed, registers, bus)
+            }
+            OpCode::NOT => not(&program.arg1.parsed, registers),
+            OpCode::NEG => neg(&program.arg1.parsed, registers),
+            OpCode::SHL
+            | OpCode::SHR
+            | OpCode::SAR
+            | OpCode::ROL
+            | OpCode::ROR
+            | OpCode::RCL
+            | OpCode::RCR => shift(
+                program.opcode,
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                registers,
+            ),
+            OpCode::HALT => return Ok(StepOutcome::Halted),
+            OpCode::NOP => Ok(()),
+            OpCode::WDM => wdm(
+                &program.arg1.parsed,
+                &program.arg2.parsed,
+                &program.arg3.parsed,
+                &mut self.display,
+                registers,
+                bus,
+            ),
+            op if op.is_branch() => branch(op, &program.arg1.parsed, registers),
+            OpCode::PUSH => push(&program.arg1.parsed, registers, bus),
+            OpCode::POP => pop(&program.arg1.parsed, registers, bus),
+            OpCode::CALL => call(&program.arg1.parsed, registers, bus),
+            OpCode::RET => ret(registers, bus),
+            OpCode::EI => write_flag(registers, FLAG_INTERRUPT, true),
+            OpCode::DI => write_flag(registers, FLAG_INTERRUPT, false),
+            OpCode::IRET => iret(registers, bus),
+            OpCode::VEC => vec(&program.arg1.parsed, &program.arg2.parsed, bus),
+            OpCode::TMR => {
+                tmr(&program.arg1.parsed, &mut self.timer, registers, bus)
+            }
+            _ => Err(RizeError {
+                type_: RizeErrorType::Execute,
+                message: format!("OpCode {:?} not implemented", program.opcode),
+            }),
+        }?;
+
+        if counts_for_timer && self.timer.tick() {
+            self.interrupts.raise(TIMER_INTERRUPT);
+        }
+
+        Ok(StepOutcome::Continue)
+    }
+
+    /// Runs a full Fetch -> Decode -> Execute Cycle.
+    pub fn step(&mut self) -> Result<StepOutcome, RizeError> {
+        if self.fetch()? == StepOutcome::EndOfProgram {
+            return Ok(StepOutcome::EndOfProgram);
+        }
+        self.decode()?;
+        let outcome = self.execute()?;
+
+        if outcome == StepOutcome::Continue {
+            self.service_interrupt()?;
+        }
+        Ok(outcome)
+    }
+
+    /// Steps until the Program halts, ends, or an Error occurs.
+    pub fn run(&mut self) -> Result<StepOutcome, RizeError> {
+        loop {
+            let outcome = self.step()?;
+            if outcome != StepOutcome::Continue {
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// Steps at most `max_instructions` times,
+    /// stopping right away once the Program halts, ends, or an Error occurs.
+    ///
+    /// ### Dev Metadata
+    /// - an Instruction counts as executed once its Execute Stage succeeded,
+    ///   so a final 'HALT' is counted, but a failing Instruction is not
+    pub fn run_batch(&mut self, max_instructions: usize) -> BatchReport {
+        let mut executed = 0;
+
+        for _ in 0..max_instructions {
+            let outcome = match self.step() {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    return BatchReport {
+                        executed,
+                        stop: Some(StopReason::Error(e)),
+                    }
+                }
+            };
+
+            let stop = match outcome {
+                StepOutcome::Continue => {
+                    executed += 1;
+                    continue;
+                }
+                StepOutcome::Halted => {
+                    executed += 1;
+                    StopReason::Halted
+                }
+                StepOutcome::EndOfProgram => StopReason::EndOfProgram,
+            };
+
+            return BatchReport {
+                executed,
+                stop: Some(stop),
+            };
+        }
+
+        BatchReport {
+            executed,
+            stop: None,
+        }
+    }
+}
+
+/// Why a [Cpu::run_batch] call stopped before using up its Instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StopReason {
+    Halted,
+    EndOfProgram,
+    Error(RizeError),
+}
+
+/// Result of a [Cpu::run_batch] call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchReport {
+    pub executed: usize,
+    /// `None` if all requested Instructions ran.
+    pub stop: Option<StopReason>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str) -> Cpu {
+        let mut cpu = Cpu::new();
+        cpu.load_program(source).expect("assembles");
+        let report = cpu.run_batch(10_000);
+        assert_eq!(report.stop, Some(StopReason::Halted), "{}", source);
+        cpu
+    }
+
+    fn run_error(source: &str) -> RizeError {
+        let mut cpu = Cpu::new();
+        cpu.load_program(source).expect("assembles");
+        match cpu.run_batch(10_000).stop {
+            Some(StopReason::Error(e)) => e,
+            other => panic!("expected an Error, got {:?}", other),
+        }
+    }
+
+    fn run_file(name: &str, max_instructions: usize) -> Cpu {
+        let mut cpu = Cpu::new();
+        cpu.load_program_file(&Path::new(AZZEMBLY_DIR).join(name))
+            .expect("assembles");
+        cpu.run_batch(max_instructions);
+        cpu
+    }
+
+    fn read(cpu: &mut Cpu, name: &str) -> u16 {
+        cpu.registers
+            .get(name)
+            .unwrap_or_else(|| panic!("no Register '{}'", name))
+            .read_section_u16()
+            .unwrap()
+    }
+
+    /// Runs `CMP a b` followed by `jump`, and tells whether it was taken.
+    fn branches(a: &str, b: &str, jump: &str) -> bool {
+        let mut cpu = run(&format!(
+            "MOV GAA {a}\nCMP GAA {b}\n{jump} .yes\nMOV GBA 1\nHALT\n.yes\nMOV GBA 2\nHALT"
+        ));
+        read(&mut cpu, "gba") == 2
+    }
+
+    #[test]
+    fn runs_without_a_frontend() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("MOV GAA 3\nADD GAA 4\nHALT")
+            .expect("assembles");
+        assert_eq!(cpu.step(), Ok(StepOutcome::Continue));
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+        assert_eq!(cpu.step(), Ok(StepOutcome::Continue));
+        assert_eq!(cpu.step(), Ok(StepOutcome::Halted));
+        assert_eq!(read(&mut cpu, "gaa"), 7);
+    }
+
+    #[test]
+    fn stops_at_halt() {
+        let mut cpu = run("MOV GAA 3\nHALT\nMOV GAA 4\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+    }
+
+    #[test]
+    fn load_program_stores_the_encoded_program() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("MOV GAA 3\nHALT").expect("assembles");
+        let words = assemble("MOV GAA 3\nHALT").unwrap().words;
+        for (address, word) in words.iter().enumerate() {
+            assert_eq!(cpu.bus.read(PROGRAM_START + address as u16), Ok(*word));
+        }
+    }
+
+    #[test]
+    fn run_batch_counts_executed_instructions() {
+        let mut cpu = Cpu::new();
+        cpu.load_program(".loop\nADD GAA 1\nJMP .loop")
+            .expect("assembles");
+        let report = cpu.run_batch(10);
+        assert_eq!(report.executed, 10);
+        assert_eq!(report.stop, None);
+        assert_eq!(read(&mut cpu, "gaa"), 5);
+
+        let mut cpu = Cpu::new();
+        cpu.load_program("NOP\nHALT").expect("assembles");
+        let report = cpu.run_batch(10);
+        assert_eq!(report.executed, 2);
+        assert_eq!(report.stop, Some(StopReason::Halted));
+
+        let mut cpu = Cpu::new();
+        cpu.load_program("NOP\nNOP").expect("assembles");
+        let report = cpu.run_batch(10);
+        assert_eq!(report.executed, 2);
+        assert_eq!(report.stop, Some(StopReason::EndOfProgram));
+    }
+
+    #[test]
+    fn run_batch_does_not_count_a_failing_instruction() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("NOP\nMOV GAA 1\nDIV GAA 0")
+            .expect("assembles");
+        let report = cpu.run_batch(10);
+        assert_eq!(report.executed, 2);
+        assert!(matches!(report.stop, Some(StopReason::Error(_))));
+    }
+
+    #[test]
+    fn push_and_pop_move_the_stack_pointer() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("MOV GAA 7\nPUSH GAA\nPOP GBA\nHALT")
+            .expect("assembles");
+        let stack_top = cpu.bus.memory.stack_top();
+        assert_eq!(read(&mut cpu, "sp"), stack_top);
+
+        cpu.run_batch(2);
+        assert_eq!(read(&mut cpu, "sp"), stack_top - 1);
+        assert_eq!(cpu.bus.read(stack_top), Ok(7));
+
+        cpu.run_batch(2);
+        assert_eq!(read(&mut cpu, "sp"), stack_top);
+        assert_eq!(read(&mut cpu, "gba"), 7);
+    }
+
+    #[test]
+    fn stack_overflow_and_underflow() {
+        let e = run_error(".loop\nPUSH GAA\nJMP .loop");
+        assert_eq!(e.type_, RizeErrorType::StackOverflow);
+        assert!(e.message.contains("Stack Overflow"), "{}", e.message);
+
+        let e = run_error("POP GAA\nHALT");
+        assert!(e.message.contains("Stack Underflow"), "{}", e.message);
+
+        let e = run_error("RET");
+        assert!(e.message.contains("Stack Underflow"), "{}", e.message);
+    }
+
+    #[test]
+    fn runs_subroutines_sample() {
+        let mut cpu = run_file("Subroutines.azm", 1_000);
+        assert_eq!(read(&mut cpu, "gba"), 12);
+        assert_eq!(read(&mut cpu, "sp"), cpu.bus.memory.stack_top());
+    }
+
+    #[test]
+    fn cmp_and_test_only_set_flags() {
+        let mut cpu = run("MOV GAA 3\nCMP GAA 3\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+        assert_eq!(read(&mut cpu, "fz"), 1);
+        assert_eq!(read(&mut cpu, "fc"), 0);
+
+        let mut cpu = run("MOV GAA 3\nCMP GAA 4\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+        assert_eq!(read(&mut cpu, "fz"), 0);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+        assert_eq!(read(&mut cpu, "fn"), 1);
+
+        let mut cpu = run("MOV GAA $0b1010\nTEST GAA $0b0101\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0b1010);
+        assert_eq!(read(&mut cpu, "fz"), 1);
+
+        let mut cpu = run("MOV GAA $0x8001\nTEST GAA $0x8000\nHALT");
+        assert_eq!(read(&mut cpu, "fz"), 0);
+        assert_eq!(read(&mut cpu, "fn"), 1);
+    }
+
+    #[test]
+    fn add_sets_flags() {
+        let mut cpu = run("MOV GAA $0x7FFF\nADD GAA 1\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0x8000);
+        assert_eq!(read(&mut cpu, "fo"), 1);
+        assert_eq!(read(&mut cpu, "fn"), 1);
+        assert_eq!(read(&mut cpu, "fc"), 0);
+
+        let mut cpu = run("MOV GAA $0xFFFF\nADD GAA 1\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0);
+        assert_eq!(read(&mut cpu, "fz"), 1);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+        assert_eq!(read(&mut cpu, "fo"), 0);
+    }
+
+    #[test]
+    fn flag_branches() {
+        assert!(branches("3", "3", "JIZ"));
+        assert!(!branches("3", "4", "JIZ"));
+        assert!(branches("3", "4", "JNZ"));
+        assert!(branches("3", "4", "JIC"));
+        assert!(branches("4", "3", "JNC"));
+        assert!(branches("3", "4", "JIN"));
+        assert!(branches("4", "3", "JNN"));
+        assert!(branches("$0x8000", "1", "JIO"));
+        assert!(branches("3", "4", "JNO"));
+        assert!(branches("3", "4", "JMP"));
+    }
+
+    #[test]
+    fn signed_and_unsigned_branches() {
+        assert!(branches("3", "4", "JB"));
+        assert!(!branches("3", "4", "JA"));
+        assert!(branches("3", "4", "JLT"));
+        assert!(!branches("3", "4", "JGE"));
+
+        // -1 is below 1 when signed, but above it when unsigned.
+        assert!(branches("-1", "1", "JLT"));
+        assert!(!branches("-1", "1", "JGT"));
+        assert!(branches("-1", "1", "JA"));
+        assert!(!branches("-1", "1", "JB"));
+        assert!(branches("-1", "-1", "JLE"));
+        assert!(branches("-1", "-1", "JGE"));
+        assert!(branches("$0x8000", "1", "JLT"));
+    }
+
+    #[test]
+    fn swp_swaps_registers_and_memory() {
+        let mut cpu = run("MOV GAA 1\nMOV GBA 2\nSWP GAA GBA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 2);
+        assert_eq!(read(&mut cpu, "gba"), 1);
+
+        let mut cpu = run("MOV GAA 1\nMOV GBA 2\nSWP GAA GBA GCA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 2);
+        assert_eq!(read(&mut cpu, "gba"), 1);
+        assert_eq!(read(&mut cpu, "gca"), 1);
+
+        let mut cpu = run("MOV 0x100 5\nMOV GAA 9\nSWP GAA 0x100\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 5);
+        assert_eq!(cpu.bus.read(0x100), Ok(9));
+    }
+
+    #[test]
+    fn fr_bits_are_addressed_one_by_one() {
+        let mut cpu = run("MOV FRA 1\nMOV FRH 1\nMOV FRC 1\nMOV FRC 0\nHALT");
+        assert_eq!(read(&mut cpu, "fr"), 0b1000_0001);
+        assert_eq!(read(&mut cpu, "fra"), 1);
+        assert_eq!(read(&mut cpu, "frc"), 0);
+        assert_eq!(read(&mut cpu, "frh"), 1);
+
+        let mut cpu =
+            run("MOV FRB 1\nCMP FRB 1\nJIZ .set\nHALT\n.set\nMOV GAA 1\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 1);
+    }
+
+    #[test]
+    fn creates_the_declared_gprs() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("#! gprs: 3\nHALT").expect("assembles");
+        assert!(cpu.registers.get("gca").is_some());
+        assert!(cpu.registers.get("gda").is_none());
+    }
+
+    #[test]
+    fn runs_interrupts_sample() {
+        let mut cpu = run_file("Interrupts.azm", 1_000);
+        assert_eq!(read(&mut cpu, "gaa"), 12);
+        assert_eq!(read(&mut cpu, "gba"), 4);
+    }
+
+    #[test]
+    fn short_timer_periods_still_reach_the_program() {
+        for period in 1..=3 {
+            let mut cpu = run(&format!(
+                "VEC 0 .tick\nTMR {period}\nEI\n.loop\nADD GAA 1\nCMP GAA 10\nJB .loop\nDI\nHALT\n.tick\nADD GBA 1\nIRET"
+            ));
+            assert_eq!(read(&mut cpu, "gaa"), 10);
+            assert!(read(&mut cpu, "gba") >= 30 / period);
+        }
+    }
+
+    #[test]
+    fn iret_restores_pc_and_flags() {
+        let mut cpu = run(
+            "VEC 0 .tick\nTMR 2\nEI\nCMP GAA 0\nNOP\nJIZ .done\nHALT\n.done\nMOV GBA 1\nHALT\n.tick\nCMP GAA 1\nIRET",
+        );
+        assert_eq!(read(&mut cpu, "gba"), 1);
+        assert_eq!(read(&mut cpu, "sp"), cpu.bus.memory.stack_top());
+    }
+
+    #[test]
+    fn interrupts_wait_for_ei() {
+        let mut cpu =
+            run("VEC 0 .tick\nTMR 1\nNOP\nNOP\nHALT\n.tick\nMOV GBA 1\nIRET");
+        assert_eq!(read(&mut cpu, "gba"), 0);
+    }
+
+    #[test]
+    fn runs_console_sample() {
+        let cpu = run_file("Console.azm", 1_000);
+        let numbers: String = (1..=20).map(|n| format!("{}\n", n)).collect();
+        assert_eq!(cpu.console.tail(64), Ok(format!("Hi!\n{}", numbers)));
+    }
+
+    #[test]
+    fn memory_header_places_stack_and_vectors() {
+        let mut cpu = Cpu::new();
+        cpu.load_program("#! memory: 1024\nPUSH GAA\nHALT")
+            .expect("assembles");
+        let memory = &cpu.bus.memory;
+        assert_eq!(memory.size(), 1024);
+        assert_eq!(memory.stack_top(), 1023);
+        assert_eq!(memory.stack_bottom(), 1024 - STACK_SIZE as u16);
+        assert_eq!(
+            memory.interrupt_vector_table(),
+            memory.stack_bottom() - INTERRUPT_VECTORS as u16
+        );
+        assert_eq!(read(&mut cpu, "sp"), 1023);
+
+        let mut cpu = run("NOP\nHALT");
+        assert_eq!(cpu.bus.memory.size(), MEMORY_SIZE_WORDS);
+        assert_eq!(read(&mut cpu, "sp"), MEMORY_SIZE_WORDS as u16 - 1);
+    }
+
+    #[test]
+    fn memory_size_bounds_accesses() {
+        let e = run_error("#! memory: 512\nMOV GAA 512\nST 512 GAA\nHALT");
+        assert_eq!(
+            e.message,
+            "Memory Address Out Of Range! Addr: 512, Max: 511"
+        );
+        assert!(run_error("MOV 0x1000 1\nHALT")
+            .message
+            .contains("Out Of Range"));
+    }
+
+    #[test]
+    fn rejects_programs_that_reach_the_vector_table() {
+        let program = "NOP\n".repeat(64);
+        let mut cpu = Cpu::new();
+        let e = cpu
+            .load_program(format!("#! memory: 512\n{}", program))
+            .unwrap_err();
+        assert!(
+            e.message.contains("Interrupt Vector Table"),
+            "{}",
+            e.message
+        );
+        assert!(cpu.load_program(program).is_ok());
+    }
+
+    #[test]
+    fn reads_every_section() {
+        let mut cpu = run("MOV GAA $0xABCD\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0xCD);
+        assert_eq!(read(&mut cpu, "gac"), 0xD);
+        assert_eq!(read(&mut cpu, "gad"), 0b01);
+        assert_eq!(read(&mut cpu, "gae"), 0xAB);
+        assert_eq!(read(&mut cpu, "gaf"), 0xC);
+        assert_eq!(read(&mut cpu, "gag"), 0b11);
+    }
+
+    #[test]
+    fn writes_upper_sections_in_place() {
+        let mut cpu = run("MOV GAA $0xABCD\nMOV GAE $0x12\nMOV GAF 0\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0x120D);
+
+        let mut cpu = run("MOV GAA $0xFF34\nADD GAE 1\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0x0034);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+        assert_eq!(read(&mut cpu, "fz"), 1);
+
+        let mut cpu = run("MOV GAG 3\nADD GAG GAG\nHALT");
+        assert_eq!(read(&mut cpu, "gag"), 0b10);
+        assert_eq!(read(&mut cpu, "gaa"), 0b1000);
+    }
+
+    #[test]
+    fn signed_ops() {
+        let mut cpu = run("MOV GAA -21\nIDIV GAA 2 GBA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa") as i16, -10);
+        assert_eq!(read(&mut cpu, "gba") as i16, -1);
+
+        let mut cpu = run("MOV GAA 5\nNEG GAA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa") as i16, -5);
+        assert_eq!(read(&mut cpu, "fn"), 1);
+
+        let mut cpu = run("MOV GAA $0x4000\nIMUL GAA 2\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0x8000);
+        assert_eq!(read(&mut cpu, "fo"), 1);
+
+        let mut cpu = run("MOV GAA $0x8000\nSAR GAA 3\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0xF000);
+    }
+
+    #[test]
+    fn signed_ops_use_the_section_width() {
+        let mut cpu = run("MOV GAB -4\nIDIV GAB 2\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 254);
+        assert_eq!(read(&mut cpu, "fn"), 1);
+
+        let mut cpu = run("MOV GAB -3\nIMUL GAB 3\nHALT");
+        assert_eq!(read(&mut cpu, "gab") as u8 as i8, -9);
+        assert_eq!(read(&mut cpu, "fo"), 0);
+
+        let mut cpu = run("MOV GAB 64\nIMUL GAB 2\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 128);
+        assert_eq!(read(&mut cpu, "fo"), 1);
+
+        let mut cpu = run("MOV GAB -128\nNEG GAB\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 128);
+        assert_eq!(read(&mut cpu, "fo"), 1);
+
+        let mut cpu = run(
+            "MOV GAB -1\nCMP GAB 1\nJLT .yes\nMOV GBA 1\nHALT\n.yes\nMOV GBA 2\nHALT",
+        );
+        assert_eq!(read(&mut cpu, "gba"), 2);
+    }
+
+    #[test]
+    fn mul_and_div_flags_use_the_section_width() {
+        let mut cpu = run("MOV GAB 200\nDIV GAB 2\nMUL GAB 3\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 44);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+        assert_eq!(read(&mut cpu, "fo"), 1);
+        assert_eq!(read(&mut cpu, "fn"), 0);
+
+        let mut cpu = run("MOV GAB 16\nMUL GAB 16\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0);
+        assert_eq!(read(&mut cpu, "fz"), 1);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+
+        let mut cpu = run("MOV GAB 10\nMUL GAB 12\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 120);
+        assert_eq!(read(&mut cpu, "fc"), 0);
+        assert_eq!(read(&mut cpu, "fo"), 0);
+
+        let mut cpu = run("MOV GAB 128\nDIV GAB 255\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0);
+        assert_eq!(read(&mut cpu, "fo"), 1);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+    }
+
+    #[test]
+    fn adc_and_sbb_chain_through_fc() {
+        let mut cpu =
+            run("MOV GAA $0xFFFF\nMOV GBA 1\nADD GAA 1\nADC GBA 0\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0);
+        assert_eq!(read(&mut cpu, "gba"), 2);
+
+        let mut cpu = run("MOV GAA 0\nMOV GBA 2\nSUB GAA 1\nSBB GBA 0\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0xFFFF);
+        assert_eq!(read(&mut cpu, "gba"), 1);
+
+        let mut cpu = run("MOV GAB 255\nADD GAB 1\nADC GAC 0\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 1);
+    }
+
+    #[test]
+    fn shifts_and_rotations_use_the_section_width() {
+        let mut cpu = run("MOV GAB 248\nSHL GAB 1\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 240);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+        assert_eq!(read(&mut cpu, "gaa"), 240);
+
+        let mut cpu = run("MOV GAB $0x80\nSAR GAB 1\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0xC0);
+        assert_eq!(read(&mut cpu, "fn"), 1);
+
+        let mut cpu = run("MOV GAA $0x8000\nSHR GAA 15\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 1);
+
+        let mut cpu = run("MOV GAB $0x81\nROL GAB 1\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0x03);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+
+        let mut cpu = run("MOV GAB $0x01\nROR GAB 1\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0x80);
+
+        let mut cpu = run("MOV GAA 1\nROR GAA 1\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0x8000);
+
+        let mut cpu = run("MOV GAC $0b1001\nROL GAC 2\nHALT");
+        assert_eq!(read(&mut cpu, "gac"), 0b0110);
+    }
+
+    #[test]
+    fn rotations_through_carry() {
+        let mut cpu = run("MOV GAB $0x80\nRCL GAB 1\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+
+        let mut cpu = run("MOV GAB $0x80\nRCL GAB 2\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 1);
+        assert_eq!(read(&mut cpu, "fc"), 0);
+
+        let mut cpu = run("MOV GAB 1\nRCR GAB 1\nRCR GAB 1\nHALT");
+        assert_eq!(read(&mut cpu, "gab"), 0x80);
+        assert_eq!(read(&mut cpu, "fc"), 0);
+    }
+
+    #[test]
+    fn div_and_mod() {
+        let mut cpu = run("MOV GAA 17\nDIV GAA 5\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+        assert_eq!(read(&mut cpu, "fc"), 1);
+
+        let mut cpu = run("MOV GAA 17\nDIV GAA 5 GBA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+        assert_eq!(read(&mut cpu, "gba"), 2);
+
+        let mut cpu = run("MOV GAA 17\nIDIV GAA 5 GBA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 3);
+        assert_eq!(read(&mut cpu, "gba"), 2);
+
+        let mut cpu = run("MOV GAA 17\nMOD GAA 5\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 2);
+
+        let mut cpu = run("MOV GAA 17\nMOD GAA 5 GBA\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 17);
+        assert_eq!(read(&mut cpu, "gba"), 2);
+
+        let mut cpu = run("MOV GAA 15\nMOD GAA 5\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 0);
+        assert_eq!(read(&mut cpu, "fz"), 1);
+    }
+
+    #[test]
+    fn divide_by_zero_stops_the_cpu() {
+        for op in ["DIV", "IDIV", "MOD"] {
+            let e = run_error(&format!("MOV GAA 1\n{op} GAA 0\nHALT"));
+            assert_eq!(e.message, "Division by zero");
+        }
+    }
+
+    #[test]
+    fn indirect_operands_read_and_write_memory() {
+        let mut cpu = run(
+            "MOV GAA $0x100\nMOV [GAA] 7\nMOV [GAA+4] 9\nMOV GBA [GAA]\nMOV GCA [GAA+4]\nHALT",
+        );
+        assert_eq!(cpu.bus.read(0x100), Ok(7));
+        assert_eq!(cpu.bus.read(0x104), Ok(9));
+        assert_eq!(read(&mut cpu, "gba"), 7);
+        assert_eq!(read(&mut cpu, "gca"), 9);
+        assert_eq!(read(&mut cpu, "gaa"), 0x100);
+    }
+
+    #[test]
+    fn indirect_operands_walk_arrays() {
+        let mut cpu = run(
+            "MOV GAA $.data\n.loop\nMOV GCA [GAA]\nCMP GCA 0\nJIZ .done\nADD GBA GCA\nADD GAA 1\nJMP .loop\n.done\nHALT\n.data\n.word 3 4 5 0",
+        );
+        assert_eq!(read(&mut cpu, "gba"), 12);
+
+        let mut cpu = run("MOV GAA $0x104\nMOV 0x100 5\nMOV GBA [GAA-4]\nHALT");
+        assert_eq!(read(&mut cpu, "gba"), 5);
+    }
+
+    #[test]
+    fn address_constants_read_and_write_memory() {
+        let mut cpu = run(".equ BUF 0x100\n.equ CONSOLE 0xFF02\nMOV BUF 7\nMOV GAA BUF\nMOV CONSOLE 'A'\nHALT");
+        assert_eq!(read(&mut cpu, "gaa"), 7);
+        assert_eq!(cpu.console.tail(1), Ok("A".to_string()));
+    }
+
+    #[test]
+    fn program_location_follows_macros() {
+        let mut cpu = Cpu::new();
+        cpu.load_program(".macro m\nNOP\nHALT\n.endm\nNOP\nm")
+            .expect("assembles");
+        cpu.step().unwrap();
+        assert_eq!(cpu.program.location.to_string(), "Line 5");
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(
+            cpu.program.location.to_string(),
+            "Line 6 (Macro 'm', Line 3)"
+        );
+    }
+
+    #[test]
+    fn program_location_names_the_file() {
+        let mut cpu = Cpu::new();
+        cpu.load_program_file(&Path::new(AZZEMBLY_DIR).join("Console.azm"))
+            .expect("assembles");
+        cpu.step().unwrap();
+        let location = &cpu.program.location;
+        assert_eq!(location.location.file, "Console.azm");
+        assert!(
+            location
+                .to_string()
+                .contains("of 'Console.azm' (Macro 'putc', Line ")
+                && location.to_string().ends_with(" of 'lib/console.azm')"),
+            "{}",
+            location
+        );
+    }
+
+    #[test]
+    fn program_location_names_included_files() {
+        let dir = std::env::temp_dir()
+            .join(format!("rize-include-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lib = dir.join("inc.azm");
+        fs::write(&lib, "# Adds one\nADD GAA 1\nRET").unwrap();
+
+        let mut cpu = Cpu::new();
+        cpu.load_program(format!(
+            "CALL .inc\nHALT\n.inc\n.include \"{}\"",
+            lib.display()
+        ))
+        .expect("assembles");
+        cpu.step().unwrap();
+        assert_eq!(cpu.program.location.to_string(), "Line 1");
+        cpu.step().unwrap();
+        assert_eq!(
+            cpu.program.location.to_string(),
+            format!("Line 2 of '{}'", lib.display())
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
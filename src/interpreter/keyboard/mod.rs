@@ -0,0 +1,100 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use super::*;
+use crate::*;
+
+/// Whether Key Presses go to the CPU's Keyboard.
+/// Clicking the Display focuses it, clicking anywhere else unfocuses it.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct DisplayFocus(pub bool);
+
+pub struct RizeOneKeyboard;
+
+impl Plugin for RizeOneKeyboard {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DisplayFocus::default());
+        app.register_type::<DisplayFocus>();
+
+        app.add_systems(Update, (focus_display, capture_keyboard).chain());
+    }
+}
+
+pub fn focus_display(
+    mut r_focus: ResMut<DisplayFocus>,
+    r_mouse: Res<ButtonInput<MouseButton>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform)>,
+    q_display: Query<(&GlobalTransform, &Name), With<Sprite>>,
+) {
+    if !r_mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (q_window.get_single(), q_camera.get_single())
+    else {
+        return;
+    };
+    let Some(display_transform) = q_display
+        .iter()
+        .find(|(_, name)| name.as_str() == "ui-display")
+        .map(|(transform, _)| transform)
+    else {
+        return;
+    };
+
+    let Some(cursor) = window.cursor_position().and_then(|cursor| {
+        camera.viewport_to_world_2d(camera_transform, cursor).ok()
+    }) else {
+        r_focus.0 = false;
+        return;
+    };
+
+    let (scale, _, center) = display_transform.to_scale_rotation_translation();
+    let half_size = Vec2::new(
+        DISPLAY_WIDTH as f32 * scale.x,
+        DISPLAY_HEIGHT as f32 * scale.y,
+    ) / 2.0;
+    let offset = (cursor - center.truncate()).abs();
+
+    r_focus.0 = offset.x <= half_size.x && offset.y <= half_size.y;
+}
+
+pub fn capture_keyboard(
+    r_focus: Res<DisplayFocus>,
+    r_cpu: Res<RizeCpu>,
+    mut er_keyboard: EventReader<KeyboardInput>,
+) {
+    for event in er_keyboard.read() {
+        if !r_focus.0 || event.state != ButtonState::Pressed {
+            continue;
+        }
+        if let Some(key) = key_code(&event.logical_key) {
+            r_cpu.keyboard.press(key);
+        }
+    }
+}
+
+/// ---------------- ///
+/// Helper Functions ///
+/// ---------------- ///
+
+fn key_code(key: &Key) -> Option<u16> {
+    match key {
+        Key::Character(text) => {
+            text.chars().next().filter(char::is_ascii).map(|c| c as u16)
+        }
+        Key::Space => Some(b' ' as u16),
+        Key::Enter => Some(KEY_ENTER),
+        Key::Backspace => Some(KEY_BACKSPACE),
+        Key::Escape => Some(KEY_ESCAPE),
+        Key::ArrowUp => Some(KEY_UP),
+        Key::ArrowDown => Some(KEY_DOWN),
+        Key::ArrowLeft => Some(KEY_LEFT),
+        Key::ArrowRight => Some(KEY_RIGHT),
+        _ => None,
+    }
+}
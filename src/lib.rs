@@ -0,0 +1,16 @@
+//! The Rize-1 CPU Core.
+//!
+//! Everything needed to load and run azzembly Programs,
+//! without depending on Bevy or any other Frontend.
+
+mod constants;
+pub use constants::*;
+
+mod types;
+pub use types::*;
+
+pub mod cpu;
+pub use cpu::*;
+
+pub mod assembler;
+pub use assembler::*;
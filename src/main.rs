@@ -31,11 +31,7 @@ use bevy_screen_diagnostics::{
     ScreenFrameDiagnosticsPlugin,
 };
 
-mod constants;
-pub use constants::*;
-
-mod types;
-pub use types::*;
+pub use rize_one::*;
 
 mod systems;
 pub use systems::*;
@@ -98,11 +94,8 @@ impl Plugin for RizeOne {
     fn build(&self, app: &mut App) {
         app.init_state::<CpuCycleStage>();
 
-        app.insert_resource(types::Registers::new());
-        app.insert_resource(types::Memory::new());
-
-        app.register_type::<types::Registers>();
-        app.register_type::<types::Memory>();
+        app.insert_resource(RizeCpu(Cpu::new()));
+        app.add_event::<RegistersRebuiltEvent>();
 
         app.add_systems(Startup, setup_camera);
 
@@ -110,15 +103,16 @@ impl Plugin for RizeOne {
 
         app.add_plugins(ui::RizeOneUi);
         app.add_plugins(interpreter::RizeOneInterpreter);
-
-        #[cfg(debug_assertions)]
-        app.add_plugins(
-            // StateInspectorPlugin::<CpuCycleStage>::default(),
-            ResourceInspectorPlugin::<types::Memory>::default(),
-        );
     }
 }
 
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuHaltedEvent;
+
+/// Sent whenever [Cpu::setup_registers] replaced the Register File.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistersRebuiltEvent;
+
 #[derive(States, Default, Debug, Reflect, Hash, PartialEq, Eq, Clone, Copy)]
 
 pub enum CpuCycleStage {
@@ -127,6 +121,8 @@ pub enum CpuCycleStage {
     Fetch,
     Decode,
     Execute,
+    /// Jumping into an Interrupt Handler, between two Instructions
+    Interrupt,
     AutoStep,
     Halt,
 }
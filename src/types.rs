@@ -1,18 +1,13 @@
-use bevy::prelude::*;
-use bevy_inspector_egui::prelude::*;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use crate::{interpreter::ArgType, *};
+use crate::*;
 
-#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
-pub struct CpuHaltedEvent;
-
-#[derive(Resource, Reflect, Default, Debug)]
+#[derive(Default, Debug)]
 pub struct Register {
-    #[reflect(ignore)]
     bits: Arc<Mutex<Vec<i8>>>,
-    pub section: char, // Added section field
+    pub section: char,      // Added section field
+    pub bit: Option<usize>, // Single Bit, counted from the LSB
 }
 
 impl Register {
@@ -20,7 +15,16 @@ impl Register {
         Self {
             bits: Arc::new(Mutex::new(vec![0i8; length])),
             section: 'a', // Default to full width
+            bit: None,
+        }
+    }
+
+    /// How many Bits [RegisterTrait::read_section_u16] reads.
+    pub fn section_width(&self) -> usize {
+        if self.bit.is_some() {
+            return 1;
         }
+        section_width(self.section)
     }
 }
 
@@ -29,6 +33,12 @@ pub trait RegisterTrait {
     fn read_lower_half(&self) -> Result<Vec<i8>, &'static str>;
     fn read_lower_quarter(&self) -> Result<Vec<i8>, &'static str>;
     fn read_lower_eigth(&self) -> Result<Vec<i8>, &'static str>;
+    /// Reads `width` Bits, starting `shift` Bits above the LSB.
+    fn read_bits(
+        &self,
+        shift: usize,
+        width: usize,
+    ) -> Result<Vec<i8>, &'static str>;
 
     fn read_u16(&self) -> Result<u16, &'static str>;
     fn read_ascii(&self) -> Result<String, &'static str>;
@@ -41,12 +51,21 @@ pub trait RegisterTrait {
     fn write_lower_half(&self, value: Vec<i8>) -> Result<(), RizeError>;
     fn write_lower_quarter(&self, value: Vec<i8>) -> Result<(), RizeError>;
     fn write_lower_eigth(&self, value: Vec<i8>) -> Result<(), RizeError>;
+    /// Writes `value`, starting `shift` Bits above the LSB.
+    fn write_bits(&self, shift: usize, value: Vec<i8>)
+        -> Result<(), RizeError>;
 
     /// Reads the u16 value from the register, respecting its current section setting.
     fn read_section_u16(&self) -> Result<u16, RizeError>;
 
     /// Writes a u16 value to the register, respecting its current section setting.
     fn write_section_u16(&self, value: u16) -> Result<(), RizeError>;
+
+    /// Reads the whole register as consecutive 16-bit words, MSB first.
+    fn read_words(&self) -> Result<Vec<u16>, RizeError>;
+
+    /// Fills the whole register with consecutive 16-bit words, MSB first.
+    fn store_words(&self, words: &[u16]) -> Result<(), RizeError>;
 }
 
 impl RegisterTrait for Register {
@@ -69,6 +88,19 @@ impl RegisterTrait for Register {
         let bits = self.read()?;
         Ok(bits[CPU_BITTAGE * 7 / 8..].to_vec())
     }
+    fn read_bits(
+        &self,
+        shift: usize,
+        width: usize,
+    ) -> Result<Vec<i8>, &'static str> {
+        let bits = self.read()?;
+        let end = bits
+            .len()
+            .checked_sub(shift)
+            .filter(|end| *end >= width)
+            .ok_or("Bits out of range for read_bits")?;
+        Ok(bits[end - width..end].to_vec())
+    }
 
     fn read_u16(&self) -> Result<u16, &'static str> {
         let bits = self.read()?;
@@ -182,13 +214,54 @@ impl RegisterTrait for Register {
         Ok(())
     }
 
+    fn write_bits(
+        &self,
+        shift: usize,
+        value: Vec<i8>,
+    ) -> Result<(), RizeError> {
+        let mut bits = self.bits.lock().map_err(|_| RizeError {
+            type_: RizeErrorType::RegisterWrite,
+            message: "Failed to acquire lock for write_bits".to_string(),
+        })?;
+        let Some(end) = bits
+            .len()
+            .checked_sub(shift)
+            .filter(|end| *end >= value.len())
+        else {
+            return Err(RizeError {
+                type_: RizeErrorType::RegisterWrite,
+                message: format!(
+                    "Invalid range for write_bits: {} bits at shift {}, register has {}",
+                    value.len(),
+                    shift,
+                    bits.len()
+                ),
+            });
+        };
+        bits[end - value.len()..end].copy_from_slice(&value);
+        Ok(())
+    }
+
     /// Reads the u16 value from the register, respecting its current section setting.
+    /// See [Registers::get] for what each Section means.
     fn read_section_u16(&self) -> Result<u16, RizeError> {
+        if let Some(bit) = self.bit {
+            let bits = self.read().map_err(|e| RizeError {
+                type_: RizeErrorType::RegisterRead,
+                message: e.to_string(),
+            })?;
+            let index = bit_index(bits.len(), bit)?;
+            return Ok(bits[index] as u16);
+        }
+
         let bits_result = match self.section {
             'a' => self.read(),
             'b' => self.read_lower_half(),
             'c' => self.read_lower_quarter(),
             'd' => self.read_lower_eigth(),
+            'e' => self.read_bits(CPU_BITTAGE / 2, CPU_BITTAGE / 2),
+            'f' => self.read_bits(CPU_BITTAGE / 4, CPU_BITTAGE / 4),
+            'g' => self.read_bits(CPU_BITTAGE / 8, CPU_BITTAGE / 8),
             invalid_section => {
                 return Err(RizeError {
                     type_: RizeErrorType::RegisterRead,
@@ -214,6 +287,17 @@ impl RegisterTrait for Register {
 
     /// Writes a u16 value to the register, respecting its current section setting.
     fn write_section_u16(&self, value: u16) -> Result<(), RizeError> {
+        if let Some(bit) = self.bit {
+            let mut bits = self.bits.lock().map_err(|_| RizeError {
+                type_: RizeErrorType::RegisterWrite,
+                message: "Failed to acquire lock for write_section_u16"
+                    .to_string(),
+            })?;
+            let index = bit_index(bits.len(), bit)?;
+            bits[index] = (value & 1) as i8;
+            return Ok(());
+        }
+
         match self.section {
             'a' => self.store_immediate(value as usize),
             'b' => {
@@ -228,6 +312,18 @@ impl RegisterTrait for Register {
                 let bits = u16_to_bits(value, CPU_BITTAGE / 8);
                 self.write_lower_eigth(bits)
             }
+            'e' => {
+                let bits = u16_to_bits(value, CPU_BITTAGE / 2);
+                self.write_bits(CPU_BITTAGE / 2, bits)
+            }
+            'f' => {
+                let bits = u16_to_bits(value, CPU_BITTAGE / 4);
+                self.write_bits(CPU_BITTAGE / 4, bits)
+            }
+            'g' => {
+                let bits = u16_to_bits(value, CPU_BITTAGE / 8);
+                self.write_bits(CPU_BITTAGE / 8, bits)
+            }
             invalid_section => Err(RizeError {
                 type_: RizeErrorType::RegisterWrite,
                 message: format!(
@@ -237,11 +333,42 @@ impl RegisterTrait for Register {
             }),
         } // This directly returns Result<(), RizeError>
     }
+
+    fn read_words(&self) -> Result<Vec<u16>, RizeError> {
+        let bits = self.read().map_err(|e| RizeError {
+            type_: RizeErrorType::RegisterRead,
+            message: e.to_string(),
+        })?;
+        Ok(bits.chunks(CPU_BITTAGE).map(bits_to_u16).collect())
+    }
+
+    fn store_words(&self, words: &[u16]) -> Result<(), RizeError> {
+        let mut bits = self.bits.lock().map_err(|_| RizeError {
+            type_: RizeErrorType::RegisterWrite,
+            message: "Failed to acquire lock for store_words".to_string(),
+        })?;
+        if words.len() * CPU_BITTAGE != bits.len() {
+            return Err(RizeError {
+                type_: RizeErrorType::RegisterWrite,
+                message: format!(
+                    "Invalid length for store_words: expected {} bits, got {}",
+                    bits.len(),
+                    words.len() * CPU_BITTAGE
+                ),
+            });
+        }
+        let new_bits: Vec<i8> = words
+            .iter()
+            .flat_map(|word| u16_to_bits(*word, CPU_BITTAGE))
+            .collect();
+        bits.copy_from_slice(&new_bits);
+        Ok(())
+    }
 }
 
 /// # Inner Structure with Labels
 /// - HashMap<Name, RegisterInstance>
-#[derive(Resource, Reflect, Default, InspectorOptions)]
+#[derive(Default)]
 pub struct Registers {
     all: HashMap<String, Register>,
 }
@@ -262,18 +389,38 @@ impl Registers {
     ///     - 'b' -> lower half of 'a'
     ///     - 'c' -> lower half of 'b'
     ///     - 'd' -> lower half of 'c'
+    ///     - 'e' -> upper half of 'a'
+    ///     - 'f' -> upper half of 'b'
+    ///     - 'g' -> upper half of 'c'
+    ///
+    /// - If it is 'fr' followed by 'a'..'h', address that single Bit:
+    ///     - 'fra' -> bit 1 (LSB)
+    ///     - 'frh' -> bit 8 (MSB)
+    ///
     /// Finds the base register, sets its section field, and returns a mutable reference.
     pub fn get(&mut self, original_name: &str) -> Option<&mut Register> {
         let mut lookup_name = original_name.to_string();
         let mut section = 'a'; // Default section
+        let mut bit = None;
+
+        let lowercase_name = original_name.to_ascii_lowercase();
+        if let Some(bit_char) = lowercase_name.strip_prefix(FLAG_REGISTER) {
+            let mut chars = bit_char.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if ('a'..='h').contains(&c) {
+                    bit = Some(c as usize - 'a' as usize);
+                    lookup_name = FLAG_REGISTER.to_string();
+                }
+            }
+        }
 
         if let Some(first) = original_name.chars().nth(0) {
             // Make first character check case-insensitive
-            if first.to_ascii_lowercase() == 'g' && original_name.len() >= 3 {
+            if first.eq_ignore_ascii_case(&'g') && original_name.len() >= 3 {
                 if let Some(third) = original_name.chars().nth(2) {
                     let third_lower = third.to_ascii_lowercase();
                     // Make section check case-insensitive
-                    if ['a', 'b', 'c', 'd'].contains(&third_lower) {
+                    if GPR_SECTIONS.contains(&third_lower) {
                         section = third_lower; // Store lowercase section
                         lookup_name.remove(2);
                     } // else: invalid section char, defaults to 'a'
@@ -283,22 +430,10 @@ impl Registers {
 
         let lookup_key = lookup_name.to_ascii_lowercase();
 
-        // Check for key existence before mutable borrow
-        if self.all.contains_key(&lookup_key) {
-            // Key exists, so get_mut is safe and returns Some.
-            // We can unwrap directly here, although get_mut is still safer.
-            let base_register = self.all.get_mut(&lookup_key).unwrap(); // Or use get_mut again if paranoid
-            base_register.section = section;
-            Some(base_register)
-        } else {
-            // Key doesn't exist, log immutably.
-            warn!(
-                "Register key '{}' not found in map. Available keys: {:?}",
-                lookup_key,
-                self.all.keys() // Immutable borrow here
-            );
-            None
-        }
+        let base_register = self.all.get_mut(&lookup_key)?;
+        base_register.section = section;
+        base_register.bit = bit;
+        Some(base_register)
     }
 
     pub fn all(&self) -> &HashMap<String, Register> {
@@ -324,27 +459,68 @@ impl BitToString for i8 {
     }
 }
 
-#[derive(
-    Default, Debug, Eq, PartialEq, Resource, Reflect, InspectorOptions,
-)]
+/// ### Dev Metadata
+/// | Addresses                             | Content                  |
+/// | ------------------------------------- | ------------------------ |
+/// | `PROGRAM_START..`                     | Program                  |
+/// | `interrupt_vector_table()..+8`        | Interrupt Vector Table   |
+/// | `stack_bottom()..=stack_top()`        | Stack, growing downwards |
+#[derive(Debug, Eq, PartialEq)]
 pub struct Memory {
     bytes: HashMap<usize, u16>,
+    size: usize,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Memory {
     pub fn new() -> Self {
+        Self::with_size(MEMORY_SIZE_WORDS)
+    }
+
+    /// `size` has to be within [MIN_MEMORY_SIZE]..=[MAX_MEMORY_SIZE].
+    pub fn with_size(size: usize) -> Self {
         Self {
             bytes: HashMap::new(),
+            size: size.clamp(MIN_MEMORY_SIZE, MAX_MEMORY_SIZE),
         }
     }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn stack_top(&self) -> u16 {
+        (self.size - 1) as u16
+    }
+
+    pub fn stack_bottom(&self) -> u16 {
+        (self.size - STACK_SIZE) as u16
+    }
+
+    pub fn interrupt_vector_table(&self) -> u16 {
+        self.stack_bottom() - INTERRUPT_VECTORS as u16
+    }
+
+    /// Address and Value of every Word written so far, in no particular Order.
+    pub fn written(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.bytes
+            .iter()
+            .map(|(address, data)| (*address as u16, *data))
+    }
+
     pub fn write(&mut self, address: u16, data: u16) -> Result<(), RizeError> {
-        if address as usize >= MEMORY_SIZE_BYTES {
+        if address as usize >= self.size {
             return Err(RizeError {
                 type_: RizeErrorType::MemoryWrite,
                 message: format!(
                     "Memory Address Out Of Range! Addr: {}, Max: {}",
                     address,
-                    MEMORY_SIZE_BYTES - 1
+                    self.size - 1
                 ),
             });
         }
@@ -354,13 +530,13 @@ impl Memory {
     }
 
     pub fn read(&self, address: u16) -> Result<u16, RizeError> {
-        if address as usize >= MEMORY_SIZE_BYTES {
+        if address as usize >= self.size {
             return Err(RizeError {
                 type_: RizeErrorType::MemoryRead,
                 message: format!(
                     "Memory Address Out Of Range! Addr: {}, Max: {}",
                     address,
-                    MEMORY_SIZE_BYTES - 1
+                    self.size - 1
                 ),
             });
         }
@@ -369,18 +545,11 @@ impl Memory {
     }
 }
 
-#[derive(
-    Debug,
-    PartialEq,
-    Eq,
-    Clone,
-    Copy,
-    Resource,
-    Default,
-    Reflect,
-    InspectorOptions,
-)]
-#[reflect(Resource, InspectorOptions)]
+/// ### Dev Metadata
+/// The Discriminant is the OpCode Byte used in encoded Instructions,
+/// see [crate::cpu::encode_instruction]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[repr(u8)]
 pub enum OpCode {
     #[default]
     None,
@@ -404,6 +573,130 @@ pub enum OpCode {
     JIZ,
     JIN,
     WDM,
+    PUSH,
+    POP,
+    CALL,
+    RET,
+    CMP,
+    TEST,
+    JNZ,
+    JIC,
+    JNC,
+    JIO,
+    JNO,
+    JNN,
+    JGT,
+    JLT,
+    JGE,
+    JLE,
+    JA,
+    JB,
+    EI,
+    DI,
+    IRET,
+    VEC,
+    TMR,
+    IMUL,
+    IDIV,
+    NEG,
+    SAR,
+    ADC,
+    SBB,
+    ROL,
+    ROR,
+    RCL,
+    RCR,
+    MOD,
+}
+
+impl OpCode {
+    pub const ALL: &'static [OpCode] = &[
+        OpCode::None,
+        OpCode::LD,
+        OpCode::ST,
+        OpCode::SWP,
+        OpCode::MOV,
+        OpCode::ADD,
+        OpCode::SUB,
+        OpCode::MUL,
+        OpCode::DIV,
+        OpCode::NOT,
+        OpCode::AND,
+        OpCode::OR,
+        OpCode::XOR,
+        OpCode::SHL,
+        OpCode::SHR,
+        OpCode::HALT,
+        OpCode::NOP,
+        OpCode::JMP,
+        OpCode::JIZ,
+        OpCode::JIN,
+        OpCode::WDM,
+        OpCode::PUSH,
+        OpCode::POP,
+        OpCode::CALL,
+        OpCode::RET,
+        OpCode::CMP,
+        OpCode::TEST,
+        OpCode::JNZ,
+        OpCode::JIC,
+        OpCode::JNC,
+        OpCode::JIO,
+        OpCode::JNO,
+        OpCode::JNN,
+        OpCode::JGT,
+        OpCode::JLT,
+        OpCode::JGE,
+        OpCode::JLE,
+        OpCode::JA,
+        OpCode::JB,
+        OpCode::EI,
+        OpCode::DI,
+        OpCode::IRET,
+        OpCode::VEC,
+        OpCode::TMR,
+        OpCode::IMUL,
+        OpCode::IDIV,
+        OpCode::NEG,
+        OpCode::SAR,
+        OpCode::ADC,
+        OpCode::SBB,
+        OpCode::ROL,
+        OpCode::ROR,
+        OpCode::RCL,
+        OpCode::RCR,
+        OpCode::MOD,
+    ];
+
+    pub fn to_byte(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        Self::ALL.iter().copied().find(|op| op.to_byte() == byte)
+    }
+
+    /// Whether this is JMP or one of the Flag-driven conditional Jumps.
+    pub fn is_branch(self) -> bool {
+        matches!(
+            self,
+            OpCode::JMP
+                | OpCode::JIZ
+                | OpCode::JNZ
+                | OpCode::JIC
+                | OpCode::JNC
+                | OpCode::JIO
+                | OpCode::JNO
+                | OpCode::JIN
+                | OpCode::JNN
+                | OpCode::JGT
+                | OpCode::JLT
+                | OpCode::JGE
+                | OpCode::JLE
+                | OpCode::JA
+                | OpCode::JB
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -434,6 +727,40 @@ impl std::str::FromStr for OpCode {
             "JMP" => Ok(OpCode::JMP),
             "JIZ" => Ok(OpCode::JIZ),
             "JIN" => Ok(OpCode::JIN),
+            "PUSH" => Ok(OpCode::PUSH),
+            "POP" => Ok(OpCode::POP),
+            "CALL" => Ok(OpCode::CALL),
+            "RET" => Ok(OpCode::RET),
+            "CMP" => Ok(OpCode::CMP),
+            "TEST" => Ok(OpCode::TEST),
+            "JNZ" => Ok(OpCode::JNZ),
+            "JIC" => Ok(OpCode::JIC),
+            "JNC" => Ok(OpCode::JNC),
+            "JIO" => Ok(OpCode::JIO),
+            "JNO" => Ok(OpCode::JNO),
+            "JNN" => Ok(OpCode::JNN),
+            "JGT" => Ok(OpCode::JGT),
+            "JLT" => Ok(OpCode::JLT),
+            "JGE" => Ok(OpCode::JGE),
+            "JLE" => Ok(OpCode::JLE),
+            "JA" => Ok(OpCode::JA),
+            "JB" => Ok(OpCode::JB),
+            "EI" => Ok(OpCode::EI),
+            "DI" => Ok(OpCode::DI),
+            "IRET" => Ok(OpCode::IRET),
+            "VEC" => Ok(OpCode::VEC),
+            "TMR" => Ok(OpCode::TMR),
+            "IMUL" => Ok(OpCode::IMUL),
+            "IDIV" => Ok(OpCode::IDIV),
+            "NEG" => Ok(OpCode::NEG),
+            "SAR" => Ok(OpCode::SAR),
+            "ADC" => Ok(OpCode::ADC),
+            "SBB" => Ok(OpCode::SBB),
+            "ROL" => Ok(OpCode::ROL),
+            "ROR" => Ok(OpCode::ROR),
+            "RCL" => Ok(OpCode::RCL),
+            "RCR" => Ok(OpCode::RCR),
+            "MOD" => Ok(OpCode::MOD),
             _ => Err(ParseOpCodeError),
         }
     }
@@ -441,6 +768,7 @@ impl std::str::FromStr for OpCode {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RizeErrorType {
+    Assemble,
     Fetch,
     Decode,
     Execute,
@@ -448,6 +776,9 @@ pub enum RizeErrorType {
     MemoryRead,
     RegisterRead,
     RegisterWrite,
+    StackOverflow,
+    StackUnderflow,
+    Device,
     Display,
 }
 
@@ -457,6 +788,27 @@ pub struct RizeError {
     pub message: String,
 }
 
+/// How many Bits a Section covers, see [Registers::get].
+pub fn section_width(section: char) -> usize {
+    match section {
+        'b' | 'e' => CPU_BITTAGE / 2,
+        'c' | 'f' => CPU_BITTAGE / 4,
+        'd' | 'g' => CPU_BITTAGE / 8,
+        _ => CPU_BITTAGE,
+    }
+}
+
+/// Converts a Bit counted from the LSB into an Index into MSB-first `bits`.
+fn bit_index(len: usize, bit: usize) -> Result<usize, RizeError> {
+    if bit >= len {
+        return Err(RizeError {
+            type_: RizeErrorType::RegisterRead,
+            message: format!("Bit {} is outside a {}-bit register.", bit, len),
+        });
+    }
+    Ok(len - 1 - bit)
+}
+
 /// Converts a slice of bits (i8) into a u16, zero-extending if necessary.
 /// Assumes MSB is at index 0.
 fn bits_to_u16(bits: &[i8]) -> u16 {
@@ -479,30 +831,15 @@ fn u16_to_bits(value: u16, num_bits: usize) -> Vec<i8> {
     let mut bits = vec![0i8; num_bits];
     let start_bit_index_u16 = CPU_BITTAGE.saturating_sub(num_bits);
 
-    for i in 0..num_bits {
+    for (i, bit) in bits.iter_mut().enumerate() {
         // Corresponding bit index in the full u16 (from the left/MSB)
         let u16_idx = start_bit_index_u16 + i;
         // Bit position from the right (LSB=0) in the u16
         let bit_pos_from_lsb = CPU_BITTAGE - 1 - u16_idx;
 
         if (value >> bit_pos_from_lsb) & 1 == 1 {
-            bits[i] = 1;
+            *bit = 1;
         }
     }
     bits
 }
-
-trait PosNegTrait {
-    fn is_negative(self) -> bool;
-    fn is_positive(self) -> bool;
-}
-
-impl PosNegTrait for i32 {
-    fn is_negative(self) -> bool {
-        self < 0
-    }
-
-    fn is_positive(self) -> bool {
-        self >= 0
-    }
-}
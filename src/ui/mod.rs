@@ -29,6 +29,7 @@ impl Plugin for RizeOneUi {
                     setup_available_programs,
                     setup_instruction_ui,
                     setup_display,
+                    setup_console,
                 ),
             )
                 .chain(),
@@ -41,8 +42,10 @@ impl Plugin for RizeOneUi {
                 update_register_parsed,
                 // update_control_panel,
                 available_programs,
+                rebuild_ui_registers.after(available_programs),
                 update_instruction_ui,
                 update_display,
+                update_console,
                 (update_control_panel).after(TextInputSystem),
             ),
         );
@@ -18,16 +18,34 @@ pub fn setup_ui_root(mut commands: Commands) {
     commands.spawn((UiRoot::init(), Name::new("ui-root")));
 }
 
+/// Names of the Containers [spawn_ui_registers] puts the Registers into.
+const REGISTER_CONTAINERS: [&str; 5] = [
+    "gp-container",
+    "sp-container",
+    "flag_container",
+    "fr-container",
+    "misc-container",
+];
+
 pub fn setup_ui_registers(
     mut commands: Commands,
-    r_registers: Res<Registers>,
+    r_cpu: Res<RizeCpu>,
     q_ui_root: Query<Entity, With<UiRoot>>,
 ) {
-    let registers = r_registers.all();
     let ui_root = q_ui_root.get_single().expect(
         "Querying the Ui Root before setting it up should be impossible...",
     );
 
+    spawn_ui_registers(&mut commands, &r_cpu.registers, ui_root);
+}
+
+fn spawn_ui_registers(
+    commands: &mut Commands,
+    registers: &Registers,
+    ui_root: Entity,
+) {
+    let registers = registers.all();
+
     let gp_container: Entity = commands
         .spawn((
             NodeBuilder::panel()
@@ -71,6 +89,20 @@ pub fn setup_ui_registers(
         ))
         .id();
 
+    let fr_container: Entity = commands
+        .spawn((
+            NodeBuilder::panel()
+                .absolute()
+                .float("left")
+                .border(UiRect::all(Val::Px(2.0)))
+                .padding(UiRect::all(Val::Px(8.0)))
+                .build(),
+            Name::new("fr-container"),
+            border_color(None),
+        ))
+        .with_child(create_text_node("General Purpose Flags (frh..fra)"))
+        .id();
+
     let misc_container: Entity = commands
         .spawn((
             NodeBuilder::panel().float("top").build(),
@@ -81,6 +113,7 @@ pub fn setup_ui_registers(
     commands.entity(ui_root).add_child(gp_container);
     commands.entity(ui_root).add_child(sp_container);
     commands.entity(ui_root).add_child(flag_container);
+    commands.entity(ui_root).add_child(fr_container);
 
     for register in registers {
         let register_col: Entity = commands
@@ -141,6 +174,24 @@ pub fn setup_ui_registers(
             ))
             .id();
 
+        // the 'e' Section of a GPR is its upper Byte, 'b' its lower Byte
+        if register.0.starts_with('g') {
+            commands
+                .entity(register_conversions)
+                .with_child((
+                    Text::new("Hi"),
+                    Name::new(format!("ui-{}-hi", register.0)),
+                    UiText,
+                    UiConversion,
+                ))
+                .with_child((
+                    Text::new("Lo"),
+                    Name::new(format!("ui-{}-lo", register.0)),
+                    UiText,
+                    UiConversion,
+                ));
+        }
+
         commands
             .entity(register_col)
             .add_children(&[register_row, register_conversions]);
@@ -148,10 +199,11 @@ pub fn setup_ui_registers(
 
         let target_container = match register.0.as_str().chars().next().unwrap()
         {
+            _ if register.0 == FLAG_REGISTER => fr_container,
             'g' => gp_container,
             'f' => flag_container,
-            // MAR, MDR, PC, IR
-            'm' | 'p' | 'i' => sp_container,
+            // MAR, MDR, PC, IR, SP
+            'm' | 'p' | 'i' | 's' => sp_container,
             _ => misc_container,
         };
 
@@ -170,7 +222,7 @@ pub fn setup_instruction_ui(
             "ui-instruction-container".into(),
             NodeBuilder::new()
                 .absolute()
-                .width(Val::Percent(35.0))
+                .width(Val::Percent(50.0))
                 .border(UiRect::all(Val::Px(2.0)))
                 .height(Val::Percent(10.0))
                 .float("top")
@@ -188,13 +240,20 @@ pub fn setup_instruction_ui(
 
     commands.entity(ui_root).add_child(ui_instruction_container);
 
-    let names = ["OPCODE", "ARG1", "ARG2", "ARG3"];
-    for name in names {
+    // The Line, with its File and Macro, needs the most Space
+    let names = [
+        ("OPCODE", 15.0),
+        ("ARG1", 15.0),
+        ("ARG2", 15.0),
+        ("ARG3", 15.0),
+        ("LINE", 40.0),
+    ];
+    for (name, width) in names {
         let container = commands
             .spawn(create_ui_node(
                 format!("ui-{name}-container"),
                 NodeBuilder::new()
-                    .width(Val::Percent(25.0))
+                    .width(Val::Percent(width))
                     .flex_direction(Column)
                     .build(),
             ))
@@ -276,6 +335,23 @@ pub fn setup_control_panel(
         .with_child((Text::new("Autostep Lines/Frame:"), UiText))
         .id();
 
+    let executed_container = commands
+        .spawn((
+            NodeBuilder::panel()
+                .width(Val::Percent(100.0))
+                .gap(8.0)
+                .build(),
+            Name::new("control-panel-executed"),
+        ))
+        .with_child((Text::new("Executed Last Frame:"), UiText))
+        .with_child((
+            Text::new(""),
+            Name::new("ui-autostep-executed"),
+            TextLayout::new_with_justify(JustifyText::Center),
+            UiText,
+        ))
+        .id();
+
     let button_container = commands
         .spawn((
             NodeBuilder::panel()
@@ -290,6 +366,7 @@ pub fn setup_control_panel(
     commands.entity(control_panel).add_children(&[
         cpu_container,
         config_container,
+        executed_container,
         button_container,
     ]);
 
@@ -448,6 +525,31 @@ pub fn setup_available_programs(
     commands.entity(ui_programs).add_child(program_container);
 }
 
+pub fn setup_console(
+    mut commands: Commands,
+    q_ui_root: Query<Entity, With<UiRoot>>,
+) {
+    let ui_root = q_ui_root.get_single().unwrap();
+
+    let ui_console = commands
+        .spawn(create_ui_node(
+            "ui-console".into(),
+            NodeBuilder::panel()
+                .float("right")
+                .width(Val::Px(360.0))
+                .height(Val::Px(CONSOLE_VISIBLE_LINES as f32 * 24.0))
+                .padding(UiRect::all(Val::Px(8.0)))
+                .border(UiRect::all(Val::Px(2.0)))
+                .absolute()
+                .build(),
+        ))
+        .with_child(create_text_node("Console:"))
+        .with_child((Text::new(""), Name::new("ui-console-text"), UiText))
+        .id();
+
+    commands.entity(ui_root).add_child(ui_console);
+}
+
 pub fn setup_display(
     mut commands: Commands,
     q_ui_root: Query<(Entity, &Name), With<UiElement>>,
@@ -501,6 +603,7 @@ pub fn setup_display(
 
 pub fn update_control_panel(
     mut r_active_program: ResMut<ActiveProgram>,
+    r_cpu: Res<RizeCpu>,
     s_current_stage: Res<State<CpuCycleStage>>,
     mut s_next_stage: ResMut<NextState<CpuCycleStage>>,
     q_button: Query<
@@ -519,6 +622,7 @@ pub fn update_control_panel(
                 CpuCycleStage::Fetch => "Fetch",
                 CpuCycleStage::Decode => "Decode",
                 CpuCycleStage::Execute => "Execute",
+                CpuCycleStage::Interrupt => "Interrupt",
                 CpuCycleStage::Halt => "Halted",
                 CpuCycleStage::AutoStep => "Auto-Step",
             }
@@ -527,6 +631,9 @@ pub fn update_control_panel(
         if name.as_str() == "ui-autostep-lines-text" {
             text.0 = r_active_program.autostep_lines.to_string();
         }
+        if name.as_str() == "ui-autostep-executed" {
+            text.0 = r_active_program.last_batch_executed.to_string();
+        }
     });
 
     // Handle Button Presses (As Needed)
@@ -592,7 +699,10 @@ pub fn update_control_panel(
                 CpuCycleStage::Decode => {
                     s_next_stage.set(CpuCycleStage::Execute);
                 }
-                CpuCycleStage::Execute => {
+                CpuCycleStage::Execute if r_cpu.interrupt_pending() => {
+                    s_next_stage.set(CpuCycleStage::Interrupt);
+                }
+                CpuCycleStage::Execute | CpuCycleStage::Interrupt => {
                     s_next_stage.set(CpuCycleStage::Fetch);
                 }
                 CpuCycleStage::Halt => {
@@ -633,9 +743,10 @@ pub fn update_control_panel(
 pub fn available_programs(
     r_programs: Res<AzmPrograms>,
     mut r_program: ResMut<ActiveProgram>,
-    mut r_registers: ResMut<Registers>,
+    mut r_cpu: ResMut<RizeCpu>,
     qe: Query<(Entity, &Name), With<UiElement>>,
     qi: Query<(&Interaction, &Name), (Changed<Interaction>, With<Button>)>,
+    mut ev_rebuilt: EventWriter<RegistersRebuiltEvent>,
     mut commands: Commands,
 ) {
     let program_container: Entity = qe
@@ -695,26 +806,49 @@ pub fn available_programs(
         {
             if *interaction == Interaction::Pressed {
                 info!("Full Path: {:?}", path_buf);
-                let program_contents: std::fs::File =
-                    std::fs::File::open(path_buf).unwrap();
 
                 r_program.as_mut().path = path_buf.clone();
                 r_program.as_mut().file_stem = button_name.clone().into();
-                r_program.as_mut().contents =
-                    std::io::read_to_string(&program_contents).unwrap();
-
-                // Reset Program Counter
-                r_program.as_mut().line = 0;
-                r_registers
-                    .get(PROGRAM_COUNTER)
-                    .unwrap()
-                    .store_immediate(0)
-                    .unwrap();
+
+                // Also recreates the Registers, from the Program's Header
+                match r_cpu.load_program_file(path_buf) {
+                    Ok(()) => {
+                        ev_rebuilt.send(RegistersRebuiltEvent);
+                    }
+                    Err(e) => {
+                        error!("Failed to load {:?}: {}", path_buf, e.message)
+                    }
+                }
             }
         }
     });
 }
 
+/// Loading a Program can change the amount of GPRs,
+/// so the Register Panels are thrown away and spawned again.
+pub fn rebuild_ui_registers(
+    mut ev_rebuilt: EventReader<RegistersRebuiltEvent>,
+    mut commands: Commands,
+    r_cpu: Res<RizeCpu>,
+    q_ui_root: Query<Entity, With<UiRoot>>,
+    q_named: Query<(Entity, &Name)>,
+) {
+    if ev_rebuilt.read().last().is_none() {
+        return;
+    }
+    let Ok(ui_root) = q_ui_root.get_single() else {
+        return;
+    };
+
+    for (entity, name) in q_named.iter() {
+        if REGISTER_CONTAINERS.contains(&name.as_str()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    spawn_ui_registers(&mut commands, &r_cpu.registers, ui_root);
+}
+
 /// ### Dev Metadata
 /// 1) for each register, try to find the corresponding ui elements
 ///     - each bit can be found by their name
@@ -722,10 +856,10 @@ pub fn available_programs(
 /// 2) update bit state with data from reg.read().iter().enumerate()
 pub fn update_registers(
     mut commands: Commands,
-    r_registers: Res<Registers>,
+    r_cpu: Res<RizeCpu>,
     mut q_ui: Query<(&mut Text, &Name), With<UiBit>>,
 ) {
-    for (name, register) in r_registers.all().iter() {
+    for (name, register) in r_cpu.registers.all().iter() {
         let bits = match register.read() {
             Ok(b) => b,
             Err(e) => {
@@ -756,10 +890,10 @@ pub fn update_registers(
 ///     - for each value type
 ///         - parse bits into value type
 pub fn update_register_parsed(
-    r_registers: Res<Registers>,
+    r_cpu: Res<RizeCpu>,
     mut q_ui: Query<(&mut Text, &Name), With<UiConversion>>,
 ) {
-    for (name, register) in r_registers.all().iter() {
+    for (name, register) in r_cpu.registers.all().iter() {
         let bits = match register.read() {
             Ok(b) => b,
             Err(e) => {
@@ -801,23 +935,39 @@ pub fn update_register_parsed(
                 break;
             }
         }
+
+        // --- Parse and Update High / Low Byte ---
+        let [hi, lo] = bits_to_u16(&bits).to_be_bytes();
+        let target_hi_name = format!("ui-{name}-hi");
+        let target_lo_name = format!("ui-{name}-lo");
+        for (mut text, ui_name) in q_ui.iter_mut() {
+            if ui_name.as_str() == target_hi_name {
+                text.0 = format!("Hi 0x{hi:02X}");
+            }
+            if ui_name.as_str() == target_lo_name {
+                text.0 = format!("Lo 0x{lo:02X}");
+            }
+        }
     }
 }
 
 pub fn update_instruction_ui(
-    r_active_program: Res<ActiveProgram>,
+    r_cpu: Res<RizeCpu>,
     mut q_ui: Query<(&mut Text, &Name), With<UiElement>>,
 ) {
-    let opcode = &r_active_program.raw_opcode;
-    let arg1 = &r_active_program.arg1.raw;
-    let arg2 = &r_active_program.arg2.raw;
-    let arg3 = &r_active_program.arg3.raw;
+    let opcode = &r_cpu.program.raw_opcode;
+    let arg1 = &r_cpu.program.arg1.raw;
+    let arg2 = &r_cpu.program.arg2.raw;
+    let arg3 = &r_cpu.program.arg3.raw;
+    // Includes the Macro the Instruction was expanded from
+    let line = &r_cpu.program.location.to_string();
 
     let instruction_parts = [
         ("OPCODE", opcode),
         ("ARG1", arg1),
         ("ARG2", arg2),
         ("ARG3", arg3),
+        ("LINE", line),
     ];
 
     for (ui_name_base, part_value) in instruction_parts.iter() {
@@ -832,14 +982,36 @@ pub fn update_instruction_ui(
     }
 }
 
+/// Shows the last [CONSOLE_VISIBLE_LINES], so the Console scrolls
+/// as soon as a Program prints more than that.
+pub fn update_console(
+    r_cpu: Res<RizeCpu>,
+    mut q_text: Query<(&mut Text, &Name), With<UiText>>,
+) {
+    let console_text = match r_cpu.console.tail(CONSOLE_VISIBLE_LINES) {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Failed to read the Console: {}", e.message);
+            return;
+        }
+    };
+
+    for (mut text, name) in q_text.iter_mut() {
+        if name.as_str() == "ui-console-text" && text.0 != console_text {
+            text.0 = console_text.clone();
+        }
+    }
+}
+
 pub fn update_display(
-    r_display: Res<DisplayMemory>,
+    r_cpu: Res<RizeCpu>,
     mut r_pixel_display: ResMut<PixelDisplay>,
     mut r_images: ResMut<Assets<Image>>,
 ) {
     for x in 0..DISPLAY_WIDTH {
         for y in 0..DISPLAY_HEIGHT {
-            let target_color = r_display.get_pixel(x as u16, y as u16).unwrap();
+            let target_color =
+                r_cpu.display.get_pixel(x as u16, y as u16).unwrap();
 
             r_pixel_display
                 .set_pixel(x as usize, y as usize, target_color, &mut r_images)